@@ -0,0 +1,39 @@
+//! Times a loop-heavy program in-process, split into loading (parse, analysis and name
+//! resolution) and execution, so process start-up does not blur the numbers.
+//!
+//! To compare two revisions, run `cargo bench --bench interpreter` on a checkout of each
+//! on the same machine and compare the `execute` lines; the best of several runs keeps
+//! scheduling noise out. Revisions from before the library API, such as the map-based
+//! frames that slots replaced, can only be timed through the binary
+//! (`target/release/PL_Final -r benches/factorial_loop.tbd > /dev/null`), and those
+//! builds log every statement to stdout, so their times mostly measure the logging.
+
+use std::io;
+use std::time::{Duration, Instant};
+
+use PL_Final::{Interpreter, Runtime};
+
+const RUNS: usize = 5;
+
+fn main() {
+    let source = include_str!("factorial_loop.tbd");
+
+    let mut load = Duration::MAX;
+    let mut execute = Duration::MAX;
+    for _ in 0..RUNS {
+        let mut runtime = Runtime::new();
+        runtime.set_output(Box::new(io::sink()));
+
+        let start = Instant::now();
+        let machine = Interpreter::with_runtime(runtime).load_source(source).expect("Benchmark program does not load.");
+        load = load.min(start.elapsed());
+
+        let start = Instant::now();
+        machine.run().expect("Benchmark program failed.");
+        execute = execute.min(start.elapsed());
+    }
+
+    println!("factorial_loop: best of {RUNS} runs");
+    println!("  load:    {load:?}");
+    println!("  execute: {execute:?}");
+}
@@ -1,29 +1,35 @@
 use std::cell::{Ref, RefCell};
 use std::ops::Deref;
 use std::rc::Rc;
-use crate::symbols::{Symbol, Symbols};
-use crate::tree::{FuncNode, ProgramNode, StmtNode};
+use log::info;
+use crate::error::AnalysisError;
+use crate::interner::Symbol;
+use crate::native::Native;
+use crate::symbols::{SymbolEntry, Symbols};
+use crate::tree::{BlockNode, FuncNode, ProgramNode, StmtNode};
 use crate::tree::StmtNode::Print;
 use crate::value::Value;
 
 
 pub struct Analyzer {
     program: Rc<ProgramNode>,
+    natives: Vec<Rc<Native>>,
 }
 
 impl Analyzer {
 
-    pub fn new(program: Rc<ProgramNode>) -> Analyzer {
-        Analyzer { program }
+    pub fn new(program: Rc<ProgramNode>, natives: &[Rc<Native>]) -> Analyzer {
+        Analyzer { program, natives: natives.to_vec() }
     }
 
-    pub fn analyze(&self) {
-        println!("[info] Analyze.");
-        self.collect_symbols_program();
+    pub fn analyze(&self) -> Result<(), AnalysisError> {
+        info!("Analyze.");
+        self.collect_symbols_program()?;
         self.reference_symbols_program();
+        Ok(())
     }
 
-    fn collect_symbols_program(&self) {
+    fn collect_symbols_program(&self) -> Result<(), AnalysisError> {
 
         // get program node symbol table
         let rc_symbols = self.program.symbols.clone();
@@ -31,36 +37,52 @@ impl Analyzer {
 
         // collect function node symbols
         for rc_func in &self.program.func_nodes {
-            let name = &rc_func.name;
+            let name = rc_func.name;
             let num_params = rc_func.parameters.len();
-            let symbol = Symbol::new(
-                name.clone(),
+            let symbol = SymbolEntry::new(
+                name,
                 Value::Func(rc_func.clone(), num_params),
-                num_params);
-            match symbols.map.insert(name.clone(), symbol) {
+                num_params,
+                symbols.map.len(),
+                None);
+            match symbols.map.insert(name, symbol) {
                 None => { /* all good */
                     Self::collect_symbols_block_function(
                         rc_func.clone(),
                         rc_symbols.clone()
-                    );
+                    )?;
                 }
-                Some(_) => { panic!("Duplicate identifier '{:}'!", name) }
+                Some(_) => { return Err(AnalysisError::DuplicateIdentifier(name)) }
             }
         }
 
         // collect let node symbols
         for rc_let in &self.program.let_nodes {
-            let name = &rc_let.name;
-            let symbol = Symbol::new(name.clone(), Value::Nil, 0);
-            match symbols.map.insert(name.clone(), symbol) {
+            let name = rc_let.name;
+            let symbol = SymbolEntry::new(name, Value::Nil, 0, symbols.map.len(), rc_let.kind);
+            match symbols.map.insert(name, symbol) {
                 None => { /* all good */  }
-                Some(_) => { panic!("Duplicate identifier '{:}'!", name) }
+                Some(_) => { return Err(AnalysisError::DuplicateIdentifier(name)) }
             }
         }
 
+        // natives fill the names the program left free
+        for rc_native in &self.natives {
+            if !symbols.map.contains_key(&rc_native.name) {
+                let symbol = SymbolEntry::new(
+                    rc_native.name,
+                    Value::Native(rc_native.clone()),
+                    rc_native.arity,
+                    symbols.map.len(),
+                    None);
+                symbols.map.insert(rc_native.name, symbol);
+            }
+        }
+
+        Ok(())
     }
 
-    fn collect_symbols_block_function(rc_func : Rc<FuncNode>, rc_symbols_global : Rc<RefCell<Symbols>>) {
+    fn collect_symbols_block_function(rc_func : Rc<FuncNode>, rc_symbols_global : Rc<RefCell<Symbols>>) -> Result<(), AnalysisError> {
 
         // get function node symbol table
         let rc_symbols = rc_func.block_node.symbols.clone();
@@ -71,25 +93,53 @@ impl Analyzer {
 
         // collect parameter symbols
         for param in & rc_func.parameters {
-            let name = & param.name;
-            let symbol = Symbol::new(name.clone(), Value::Nil, 0);
-            match symbols.map.insert( name.clone(), symbol) {
+            let name = param.name;
+            let symbol = SymbolEntry::new(name, Value::Nil, 0, symbols.map.len(), param.kind);
+            match symbols.map.insert( name, symbol) {
                 None => { /* all good */  }
-                Some(_) => { panic!("Duplicate parameter name '{:}' in function {:}!", name, rc_func.name) }
+                Some(_) => { return Err(AnalysisError::DuplicateLocal { name, function: rc_func.name }) }
             }
         }
 
         // collect let node symbols
-        for rc_stmt in & rc_func.block_node.statements {
-            if let StmtNode::Let(letNode) = rc_stmt.deref() {
-                let name = &letNode.name;
-                let symbol = Symbol::new(name.clone(), Value::Nil, 0);
-                match symbols.map.insert( name.clone(), symbol) {
-                    None => { /* all good */  }
-                    Some(_) => { panic!("Duplicate parameter name '{:}' in function {:}!", name, rc_func.name) }
+        let parameters: Vec<Symbol> = rc_func.parameters.iter().map(|param| param.name).collect();
+        Self::collect_symbols_block_lets(&rc_func.block_node, &mut symbols, &parameters, rc_func.name)
+    }
+
+    // blocks nested in if/while share the function frame, so their lets are collected as well;
+    // `enclosing` holds the names already in scope, and a name declared again in a sibling
+    // block reuses the slot of the first declaration
+    fn collect_symbols_block_lets(block: &BlockNode, symbols: &mut Symbols, enclosing: &[Symbol], func_name: Symbol) -> Result<(), AnalysisError> {
+        let mut visible = enclosing.to_vec();
+        for rc_stmt in & block.statements {
+            match rc_stmt.deref() {
+                StmtNode::Let(letNode) => {
+                    let name = letNode.name;
+                    if visible.contains(&name) {
+                        return Err(AnalysisError::DuplicateLocal { name, function: func_name });
+                    }
+                    visible.push(name);
+                    match symbols.map.get(&name) {
+                        // a slot has a single declared type, so only a matching one can be shared
+                        Some(symbol) if symbol.kind == letNode.kind => { /* reuse the slot */ }
+                        Some(_) => { return Err(AnalysisError::DuplicateLocal { name, function: func_name }) }
+                        None => {
+                            let symbol = SymbolEntry::new(name, Value::Nil, 0, symbols.map.len(), letNode.kind);
+                            symbols.map.insert(name, symbol);
+                        }
+                    }
+                }
+                StmtNode::If(ifNode) => {
+                    Self::collect_symbols_block_lets(&ifNode.block_node_true, symbols, &visible, func_name)?;
+                    Self::collect_symbols_block_lets(&ifNode.block_node_false, symbols, &visible, func_name)?;
+                }
+                StmtNode::While(whileNode) => {
+                    Self::collect_symbols_block_lets(&whileNode.block_node_true, symbols, &visible, func_name)?;
                 }
+                _ => {}
             }
         }
+        Ok(())
     }
 
     fn reference_symbols_program(&self) {
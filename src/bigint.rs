@@ -0,0 +1,259 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// each limb holds nine decimal digits, which keeps parsing and printing trivial
+const BASE: u64 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+/// Arbitrary-precision signed integer in sign-magnitude form.
+/// Limbs are little-endian base 10^9 with no trailing zero limbs; zero has no limbs.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    limbs: Vec<u32>,
+}
+
+impl BigInt {
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Parses an optionally signed run of decimal digits.
+    pub fn parse(text: &str) -> Option<BigInt> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let mut limbs = vec![];
+        let mut end = digits.len();
+        while end > 0 {
+            let start = end.saturating_sub(BASE_DIGITS);
+            limbs.push(digits[start..end].parse::<u32>().ok()?);
+            end = start;
+        }
+        Some(BigInt::from_parts(negative, limbs))
+    }
+
+    pub fn to_i32(&self) -> Option<i32> {
+        i32::try_from(self.to_i128()?).ok()
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        let mut magnitude: i128 = 0;
+        for limb in self.limbs.iter().rev() {
+            magnitude = magnitude.checked_mul(BASE as i128)?.checked_add(*limb as i128)?;
+        }
+        Some(if self.negative { -magnitude } else { magnitude })
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self.limbs.iter().rev().fold(0.0, |acc, limb| acc * BASE as f64 + *limb as f64);
+        if self.negative { -magnitude } else { magnitude }
+    }
+
+    fn from_parts(negative: bool, mut limbs: Vec<u32>) -> BigInt {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        let negative = negative && !limbs.is_empty();
+        BigInt { negative, limbs }
+    }
+
+    fn compare_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+        a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+    }
+
+    fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut result = Vec::with_capacity(a.len().max(b.len()) + 1);
+        let mut carry = 0u64;
+        for i in 0..a.len().max(b.len()) {
+            let sum = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+            result.push((sum % BASE) as u32);
+            carry = sum / BASE;
+        }
+        if carry > 0 {
+            result.push(carry as u32);
+        }
+        result
+    }
+
+    // requires |a| >= |b|
+    fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut result = Vec::with_capacity(a.len());
+        let mut borrow = 0i64;
+        for (i, limb) in a.iter().enumerate() {
+            let mut diff = *limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+            borrow = 0;
+            if diff < 0 {
+                diff += BASE as i64;
+                borrow = 1;
+            }
+            result.push(diff as u32);
+        }
+        result
+    }
+
+    fn mul_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut result = vec![0u64; a.len() + b.len()];
+        for (i, x) in a.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, y) in b.iter().enumerate() {
+                let current = result[i + j] + *x as u64 * *y as u64 + carry;
+                result[i + j] = current % BASE;
+                carry = current / BASE;
+            }
+            result[i + b.len()] += carry;
+        }
+        result.into_iter().map(|limb| limb as u32).collect()
+    }
+
+    fn mul_small(a: &[u32], factor: u32) -> Vec<u32> {
+        Self::mul_magnitude(a, &[factor])
+    }
+
+    // schoolbook long division; each quotient limb is found by binary search
+    fn div_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut quotient = vec![0u32; a.len()];
+        let mut remainder: Vec<u32> = vec![];
+        for i in (0..a.len()).rev() {
+            remainder.insert(0, a[i]);
+            while remainder.last() == Some(&0) {
+                remainder.pop();
+            }
+
+            let (mut low, mut high) = (0u32, (BASE - 1) as u32);
+            while low < high {
+                let middle = low + (high - low).div_ceil(2);
+                let product = Self::mul_small(b, middle);
+                let product = BigInt::from_parts(false, product);
+                if Self::compare_magnitude(&product.limbs, &remainder) == Ordering::Greater {
+                    high = middle - 1;
+                } else {
+                    low = middle;
+                }
+            }
+
+            quotient[i] = low;
+            if low > 0 {
+                let product = BigInt::from_parts(false, Self::mul_small(b, low));
+                remainder = BigInt::from_parts(false, Self::sub_magnitude(&remainder, &product.limbs)).limbs;
+            }
+        }
+        quotient
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> BigInt {
+        let mut magnitude = value.unsigned_abs();
+        let mut limbs = vec![];
+        while magnitude > 0 {
+            limbs.push((magnitude % BASE as u128) as u32);
+            magnitude /= BASE as u128;
+        }
+        BigInt::from_parts(value < 0, limbs)
+    }
+}
+
+impl From<i32> for BigInt {
+    fn from(value: i32) -> BigInt {
+        BigInt::from(value as i128)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.limbs.clone())
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(self.negative, BigInt::add_magnitude(&self.limbs, &other.limbs));
+        }
+        match BigInt::compare_magnitude(&self.limbs, &other.limbs) {
+            Ordering::Less => {
+                BigInt::from_parts(other.negative, BigInt::sub_magnitude(&other.limbs, &self.limbs))
+            }
+            _ => {
+                BigInt::from_parts(self.negative, BigInt::sub_magnitude(&self.limbs, &other.limbs))
+            }
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &(-other)
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::from_parts(self.negative != other.negative, BigInt::mul_magnitude(&self.limbs, &other.limbs))
+    }
+}
+
+/// Truncating division, like the primitive integer types. Panics on a zero divisor.
+impl Div for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, other: &BigInt) -> BigInt {
+        assert!(!other.is_zero(), "BigInt division by zero");
+        BigInt::from_parts(self.negative != other.negative, BigInt::div_magnitude(&self.limbs, &other.limbs))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => BigInt::compare_magnitude(&self.limbs, &other.limbs),
+            (true, true) => BigInt::compare_magnitude(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut digits = String::new();
+        match self.limbs.split_last() {
+            None => { digits.push('0'); }
+            Some((most, rest)) => {
+                digits.push_str(&most.to_string());
+                for limb in rest.iter().rev() {
+                    digits.push_str(&format!("{limb:09}"));
+                }
+            }
+        }
+        f.pad_integral(!self.negative, "", &digits)
+    }
+}
+
+impl fmt::Debug for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
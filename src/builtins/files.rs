@@ -0,0 +1,62 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::rc::Rc;
+use crate::error::RuntimeError;
+use crate::runtime::Runtime;
+use crate::value::Value;
+use super::text;
+
+/// Files inside the directory the host allows, off unless it allows one (`--allow-fs`).
+/// Paths are relative to that directory.
+///
+/// | function                  | result                                          |
+/// |---------------------------|-------------------------------------------------|
+/// | `read_file(path)`         | the whole file as a string                      |
+/// | `read_lines(path)`        | list of the lines of the file                   |
+/// | `write_file(path, text)`  | replaces the file with `text`                   |
+/// | `append_file(path, text)` | adds `text` to the end of the file              |
+/// | `file_exists(path)`       | whether the file exists                         |
+///
+/// Paths outside the directory, and every path while access is off, are runtime errors.
+pub fn register(runtime: &mut Runtime) {
+    runtime.register("read_file", 1, |runtime, arguments| {
+        let (path, file) = sandboxed("read_file", &arguments[0], runtime)?;
+        Ok(Value::Str(fs::read_to_string(file).map_err(|source| RuntimeError::File { path, source })?))
+    });
+    runtime.register("read_lines", 1, |runtime, arguments| {
+        let (path, file) = sandboxed("read_lines", &arguments[0], runtime)?;
+        let contents = fs::read_to_string(file).map_err(|source| RuntimeError::File { path, source })?;
+        Ok(Value::List(Rc::new(contents.lines().map(|line| Value::Str(line.to_string())).collect())))
+    });
+    runtime.register("write_file", 2, |runtime, arguments| {
+        write("write_file", &arguments, false, runtime)
+    });
+    runtime.register("append_file", 2, |runtime, arguments| {
+        write("append_file", &arguments, true, runtime)
+    });
+    runtime.register("file_exists", 1, |runtime, arguments| {
+        let (_, file) = sandboxed("file_exists", &arguments[0], runtime)?;
+        Ok(Value::Bool(file.is_file()))
+    });
+}
+
+fn sandboxed(function: &str, value: &Value, runtime: &Runtime) -> Result<(String, PathBuf), RuntimeError> {
+    let path = text(function, value)?;
+    let file = runtime.sandboxed(&path)?;
+    Ok((path, file))
+}
+
+fn write(function: &str, arguments: &[Value], append: bool, runtime: &Runtime) -> Result<Value, RuntimeError> {
+    let (path, file) = sandboxed(function, &arguments[0], runtime)?;
+    let contents = text(function, &arguments[1])?;
+    OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(file)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .map_err(|source| RuntimeError::File { path, source })?;
+    Ok(Value::Nil)
+}
@@ -0,0 +1,28 @@
+use crate::error::RuntimeError;
+use crate::runtime::Runtime;
+use crate::value::Value;
+use super::strings::{parse_float, parse_int};
+
+/// Reading from the runtime's input, stdin unless the host sets another source.
+///
+/// | function         | result                                                |
+/// |------------------|-------------------------------------------------------|
+/// | `read_line()`    | the next line, without its line ending                |
+/// | `read_int()`     | the next line as an integer, like `parse_int`         |
+/// | `read_float()`   | the next line as an `f32`, like `parse_float`         |
+/// | `end_of_input()` | `true` once every line has been read                  |
+///
+/// Reading past the end of the input is a runtime error.
+pub fn register(runtime: &mut Runtime) {
+    runtime.register("read_line", 0, |runtime, _| line(runtime));
+    runtime.register("read_int", 0, |runtime, _| parse_int(&line(runtime)?));
+    runtime.register("read_float", 0, |runtime, _| parse_float(&line(runtime)?));
+    runtime.register("end_of_input", 0, |runtime, _| Ok(Value::Bool(runtime.end_of_input()?)));
+}
+
+fn line(runtime: &Runtime) -> Result<Value, RuntimeError> {
+    match runtime.read_line()? {
+        Some(line) => { Ok(Value::Str(line)) }
+        None => { Err(RuntimeError::EndOfInput) }
+    }
+}
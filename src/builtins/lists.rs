@@ -0,0 +1,25 @@
+use std::rc::Rc;
+use crate::error::RuntimeError;
+use crate::runtime::Runtime;
+use crate::value::Value;
+use super::{integer, list, position};
+
+/// List functions. `len` is shared with strings.
+///
+/// | function       | result                                    |
+/// |----------------|-------------------------------------------|
+/// | `get(list, i)` | the item at position `i`, counting from 0 |
+/// | `args()`       | list of the program arguments, as strings |
+pub fn register(runtime: &mut Runtime) {
+    runtime.register("get", 2, |_, arguments| get(&arguments));
+    runtime.register("args", 0, |runtime, _| {
+        let arguments = runtime.arguments().iter().map(|argument| Value::Str(argument.clone()));
+        Ok(Value::List(Rc::new(arguments.collect())))
+    });
+}
+
+fn get(arguments: &[Value]) -> Result<Value, RuntimeError> {
+    let items = list("get", &arguments[0])?;
+    let index = position(integer("get", &arguments[1])?, items.len(), false)?;
+    Ok(items[index].clone())
+}
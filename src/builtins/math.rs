@@ -0,0 +1,122 @@
+use crate::error::RuntimeError;
+use crate::evaluator::{ArithmeticOp, Evaluator, RelationalOp};
+use crate::interner::Symbol;
+use crate::runtime::Runtime;
+use crate::value::{Type, Value};
+use super::expected;
+
+/// Math functions on any number type.
+///
+/// | function                  | integer arguments               | float arguments        |
+/// |---------------------------|---------------------------------|------------------------|
+/// | `abs`                     | same type, overflow as for `-`  | same type              |
+/// | `min`, `max`              | common type of the two          | common type of the two |
+/// | `pow`                     | integer for a natural exponent  | float                  |
+/// | `floor`, `ceil`, `round`  | unchanged                       | same type              |
+/// | `sqrt`, `sin`, `cos`,     | `f32`                           | same type              |
+/// | `log`, `exp`              |                                 |                        |
+///
+/// A float result is `f64` when an argument is `f64`, and `f32` otherwise.
+/// `sqrt` of a negative number and `log` of a number that is not positive are errors.
+pub fn register(runtime: &mut Runtime) {
+    runtime.register("abs", 1, |runtime, arguments| abs(&arguments[0], runtime));
+    runtime.register("min", 2, |runtime, arguments| pick("min", &arguments, RelationalOp::LessThanEqual, runtime));
+    runtime.register("max", 2, |runtime, arguments| pick("max", &arguments, RelationalOp::GreaterThanEqual, runtime));
+    runtime.register("pow", 2, |runtime, arguments| pow(&arguments[0], &arguments[1], runtime));
+    runtime.register("floor", 1, |_, arguments| rounded("floor", &arguments[0], f64::floor));
+    runtime.register("ceil", 1, |_, arguments| rounded("ceil", &arguments[0], f64::ceil));
+    runtime.register("round", 1, |_, arguments| rounded("round", &arguments[0], f64::round));
+    runtime.register("sqrt", 1, |_, arguments| real("sqrt", &arguments[0], |x| x >= 0.0, f64::sqrt));
+    runtime.register("sin", 1, |_, arguments| real("sin", &arguments[0], f64::is_finite, f64::sin));
+    runtime.register("cos", 1, |_, arguments| real("cos", &arguments[0], f64::is_finite, f64::cos));
+    runtime.register("log", 1, |_, arguments| real("log", &arguments[0], |x| x > 0.0, f64::ln));
+    runtime.register("exp", 1, |_, arguments| real("exp", &arguments[0], |_| true, f64::exp));
+}
+
+fn number(function: &str, value: &Value) -> Result<f64, RuntimeError> {
+    value.to_f64().ok_or_else(|| expected(function, "a number", value))
+}
+
+fn is_float(value: &Value) -> bool {
+    value.kind().is_some_and(Type::is_float)
+}
+
+// f64 if any argument is one, f32 otherwise
+fn float(result: f64, arguments: &[&Value]) -> Value {
+    if arguments.iter().any(|value| matches!(value, Value::F64(_))) {
+        Value::F64(result)
+    } else {
+        Value::F32(result as f32)
+    }
+}
+
+fn abs(value: &Value, runtime: &Runtime) -> Result<Value, RuntimeError> {
+    let x = number("abs", value)?;
+    if is_float(value) {
+        return Ok(float(x.abs(), &[value]));
+    }
+    if x >= 0.0 {
+        return Ok(value.clone());
+    }
+    // 0 - x, so the minimum of a fixed-width type overflows like any other subtraction
+    let zero = value.kind().and_then(|kind| Value::from_i128(0, kind)).unwrap_or(Value::I32(0));
+    Evaluator::arithmetic(zero, value.clone(), ArithmeticOp::Sub, runtime)
+}
+
+// the first argument when it compares `op` to the second, else the second,
+// brought to the common type of both
+fn pick(function: &str, arguments: &[Value], op: RelationalOp, runtime: &Runtime) -> Result<Value, RuntimeError> {
+    let (a, b) = (&arguments[0], &arguments[1]);
+    number(function, a)?;
+    number(function, b)?;
+    let first = matches!(Evaluator::relational(a.clone(), b.clone(), op, runtime)?, Value::Bool(true));
+    let picked = if first { a } else { b };
+    if is_float(a) || is_float(b) {
+        return Ok(float(picked.to_f64().unwrap(), &[a, b]));
+    }
+    match (a.kind(), b.kind()) {
+        (Some(kind_a), Some(kind_b)) => { picked.clone().convert(Type::common(kind_a, kind_b)) }
+        _ => { Ok(picked.clone()) }
+    }
+}
+
+fn pow(base: &Value, exponent: &Value, runtime: &Runtime) -> Result<Value, RuntimeError> {
+    let x = number("pow", base)?;
+    let y = number("pow", exponent)?;
+    let natural = exponent.to_i128().filter(|n| *n >= 0);
+    match natural {
+        Some(mut n) if !is_float(base) => {
+            // square and multiply, so each step overflows like `*` does
+            let mut result = base.kind().and_then(|kind| Value::from_i128(1, kind)).unwrap_or(Value::I32(1));
+            let mut square = base.clone();
+            while n > 0 {
+                if n % 2 == 1 {
+                    result = Evaluator::arithmetic(result, square.clone(), ArithmeticOp::Mul, runtime)?;
+                }
+                n /= 2;
+                if n > 0 {
+                    square = Evaluator::arithmetic(square.clone(), square, ArithmeticOp::Mul, runtime)?;
+                }
+            }
+            Ok(result)
+        }
+        _ => { Ok(float(x.powf(y), &[base, exponent])) }
+    }
+}
+
+fn rounded(function: &str, value: &Value, round: fn(f64) -> f64) -> Result<Value, RuntimeError> {
+    let x = number(function, value)?;
+    if is_float(value) {
+        Ok(float(round(x), &[value]))
+    } else {
+        Ok(value.clone())
+    }
+}
+
+fn real(function: &str, value: &Value, defined: fn(f64) -> bool, f: fn(f64) -> f64) -> Result<Value, RuntimeError> {
+    let x = number(function, value)?;
+    if !defined(x) {
+        return Err(RuntimeError::MathDomain { function: Symbol::intern(function), value: value.to_string() });
+    }
+    Ok(float(f(x), &[value]))
+}
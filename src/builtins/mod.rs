@@ -0,0 +1,57 @@
+use crate::error::RuntimeError;
+use crate::interner::Symbol;
+use crate::runtime::Runtime;
+use crate::value::Value;
+
+mod files;
+mod input;
+mod lists;
+mod math;
+mod random;
+mod strings;
+
+/// Registers the natives every program can call, unless it defines a function of the same name.
+pub fn prelude(runtime: &mut Runtime) {
+    math::register(runtime);
+    strings::register(runtime);
+    lists::register(runtime);
+    random::register(runtime);
+    input::register(runtime);
+    files::register(runtime);
+}
+
+// helpers shared by the natives; `function` names the native in argument errors
+
+// a string or a single character, as text
+pub fn text(function: &str, value: &Value) -> Result<String, RuntimeError> {
+    match value {
+        Value::Str(text) => { Ok(text.clone()) }
+        Value::Char(c) => { Ok(c.to_string()) }
+        _ => { Err(expected(function, "a string", value)) }
+    }
+}
+
+pub fn list<'a>(function: &str, value: &'a Value) -> Result<&'a [Value], RuntimeError> {
+    match value {
+        Value::List(items) => { Ok(items) }
+        _ => { Err(expected(function, "a list", value)) }
+    }
+}
+
+pub fn integer(function: &str, value: &Value) -> Result<i128, RuntimeError> {
+    value.to_i128().ok_or_else(|| expected(function, "an integer", value))
+}
+
+pub fn expected(function: &str, expected: &'static str, value: &Value) -> RuntimeError {
+    RuntimeError::ArgumentType { function: Symbol::intern(function), expected, found: value.type_name() }
+}
+
+// a position in 0..=length, or in 0..length when it has to name a character
+pub fn position(index: i128, length: usize, inclusive: bool) -> Result<usize, RuntimeError> {
+    let end = if inclusive { length as i128 } else { length as i128 - 1 };
+    if (0..=end).contains(&index) {
+        Ok(index as usize)
+    } else {
+        Err(RuntimeError::IndexOutOfRange { index, length })
+    }
+}
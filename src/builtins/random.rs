@@ -0,0 +1,42 @@
+use std::rc::Rc;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use crate::bigint::BigInt;
+use crate::error::RuntimeError;
+use crate::runtime::Runtime;
+use crate::value::Value;
+use super::{integer, list};
+
+/// Random numbers from the runtime's generator, which `--seed` makes repeatable.
+///
+/// | function             | result                                         |
+/// |----------------------|------------------------------------------------|
+/// | `random_int(lo, hi)` | an integer from `lo` to `hi`, both included    |
+/// | `random_float()`     | an `f32` from 0 up to, but not including, 1    |
+/// | `shuffle(list)`      | a new list with the items in random order      |
+/// | `choice(list)`       | one item of a non-empty list                   |
+pub fn register(runtime: &mut Runtime) {
+    runtime.register("random_int", 2, |runtime, arguments| random_int(&arguments, runtime));
+    runtime.register("random_float", 0, |runtime, _| Ok(Value::F32(runtime.rng().gen::<f32>())));
+    runtime.register("shuffle", 1, |runtime, arguments| {
+        let mut items = list("shuffle", &arguments[0])?.to_vec();
+        items.shuffle(&mut *runtime.rng());
+        Ok(Value::List(Rc::new(items)))
+    });
+    runtime.register("choice", 1, |runtime, arguments| {
+        let items = list("choice", &arguments[0])?;
+        match items.choose(&mut *runtime.rng()) {
+            Some(item) => { Ok(item.clone()) }
+            None => { Err(RuntimeError::IndexOutOfRange { index: 0, length: 0 }) }
+        }
+    });
+}
+
+fn random_int(arguments: &[Value], runtime: &Runtime) -> Result<Value, RuntimeError> {
+    let low = integer("random_int", &arguments[0])?;
+    let high = integer("random_int", &arguments[1])?;
+    if low > high {
+        return Err(RuntimeError::EmptyRange { low, high });
+    }
+    Ok(Value::from(BigInt::from(runtime.rng().gen_range(low..=high))))
+}
@@ -0,0 +1,117 @@
+use std::rc::Rc;
+use crate::bigint::BigInt;
+use crate::error::RuntimeError;
+use crate::runtime::Runtime;
+use crate::value::Value;
+use super::{integer, position, text};
+
+/// String functions. Positions and lengths count characters, not bytes, and start at 0.
+///
+/// | function                         | result                                             |
+/// |----------------------------------|----------------------------------------------------|
+/// | `len(s)`                         | number of characters, or of items of a list        |
+/// | `substr(s, start, length)`       | the `length` characters from `start`               |
+/// | `char_at(s, i)`                  | the character at `i`                               |
+/// | `index_of(s, part)`              | position of the first `part` in `s`, or -1         |
+/// | `split(s, separator)`            | list of the pieces between separators; an empty    |
+/// |                                  | separator splits into characters                   |
+/// | `trim(s)`                        | `s` without leading and trailing whitespace        |
+/// | `to_upper(s)`, `to_lower(s)`     | `s` in upper or lower case                         |
+/// | `replace(s, from, to)`           | `s` with every `from` replaced by `to`             |
+/// | `to_string(x)`                   | `x` as `print` shows it                            |
+/// | `parse_int(s)`, `parse_float(s)` | the number in `s`, ignoring surrounding whitespace |
+///
+/// A character works wherever a string is expected. Positions outside the string
+/// and text that is not a number are runtime errors.
+pub fn register(runtime: &mut Runtime) {
+    runtime.register("len", 1, |_, arguments| len(&arguments[0]));
+    runtime.register("substr", 3, |_, arguments| substr(&arguments));
+    runtime.register("char_at", 2, |_, arguments| char_at(&arguments));
+    runtime.register("index_of", 2, |_, arguments| index_of(&arguments));
+    runtime.register("split", 2, |_, arguments| split(&arguments));
+    runtime.register("trim", 1, |_, arguments| {
+        Ok(Value::Str(text("trim", &arguments[0])?.trim().to_string()))
+    });
+    runtime.register("to_upper", 1, |_, arguments| {
+        Ok(Value::Str(text("to_upper", &arguments[0])?.to_uppercase()))
+    });
+    runtime.register("to_lower", 1, |_, arguments| {
+        Ok(Value::Str(text("to_lower", &arguments[0])?.to_lowercase()))
+    });
+    runtime.register("replace", 3, |_, arguments| replace(&arguments));
+    runtime.register("to_string", 1, |_, arguments| Ok(Value::Str(arguments[0].to_string())));
+    runtime.register("parse_int", 1, |_, arguments| parse_int(&arguments[0]));
+    runtime.register("parse_float", 1, |_, arguments| parse_float(&arguments[0]));
+}
+
+fn len(value: &Value) -> Result<Value, RuntimeError> {
+    let length = match value {
+        Value::List(items) => { items.len() }
+        _ => { text("len", value)?.chars().count() }
+    };
+    Ok(Value::from(BigInt::from(length as i128)))
+}
+
+fn substr(arguments: &[Value]) -> Result<Value, RuntimeError> {
+    let chars: Vec<char> = text("substr", &arguments[0])?.chars().collect();
+    let start = position(integer("substr", &arguments[1])?, chars.len(), true)?;
+    let length = integer("substr", &arguments[2])?;
+    if length < 0 {
+        return Err(RuntimeError::IndexOutOfRange { index: length, length: chars.len() });
+    }
+    let end = position(start as i128 + length, chars.len(), true)?;
+    Ok(Value::Str(chars[start..end].iter().collect()))
+}
+
+fn char_at(arguments: &[Value]) -> Result<Value, RuntimeError> {
+    let chars: Vec<char> = text("char_at", &arguments[0])?.chars().collect();
+    let index = position(integer("char_at", &arguments[1])?, chars.len(), false)?;
+    Ok(Value::Char(chars[index]))
+}
+
+fn index_of(arguments: &[Value]) -> Result<Value, RuntimeError> {
+    let haystack = text("index_of", &arguments[0])?;
+    let needle = text("index_of", &arguments[1])?;
+    let index = match haystack.find(&needle) {
+        Some(byte) => { haystack[..byte].chars().count() as i32 }
+        None => { -1 }
+    };
+    Ok(Value::I32(index))
+}
+
+fn split(arguments: &[Value]) -> Result<Value, RuntimeError> {
+    let whole = text("split", &arguments[0])?;
+    let separator = text("split", &arguments[1])?;
+    let pieces: Vec<Value> = if separator.is_empty() {
+        whole.chars().map(|c| Value::Str(c.to_string())).collect()
+    } else {
+        whole.split(separator.as_str()).map(|piece| Value::Str(piece.to_string())).collect()
+    };
+    Ok(Value::List(Rc::new(pieces)))
+}
+
+fn replace(arguments: &[Value]) -> Result<Value, RuntimeError> {
+    let whole = text("replace", &arguments[0])?;
+    let from = text("replace", &arguments[1])?;
+    let to = text("replace", &arguments[2])?;
+    if from.is_empty() {
+        return Ok(Value::Str(whole));
+    }
+    Ok(Value::Str(whole.replace(&from, &to)))
+}
+
+pub fn parse_int(value: &Value) -> Result<Value, RuntimeError> {
+    let text = text("parse_int", value)?;
+    match BigInt::parse(text.trim()) {
+        Some(n) => { Ok(Value::from(n)) }
+        None => { Err(RuntimeError::InvalidNumber { text, target: "integer" }) }
+    }
+}
+
+pub fn parse_float(value: &Value) -> Result<Value, RuntimeError> {
+    let text = text("parse_float", value)?;
+    match text.trim().parse::<f32>() {
+        Ok(n) => { Ok(Value::F32(n)) }
+        Err(_) => { Err(RuntimeError::InvalidNumber { text, target: "f32" }) }
+    }
+}
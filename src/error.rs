@@ -0,0 +1,169 @@
+use std::fmt;
+use thiserror::Error;
+use crate::interner::Symbol;
+use crate::value::Type;
+
+/// A function on the call stack and the source line it was called from.
+/// The entry function has no call site.
+#[derive(Debug, Clone, Copy)]
+pub struct CallSite {
+    pub function: Symbol,
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for CallSite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => { write!(f, "{} (called from line {})", self.function, line) }
+            None => { write!(f, "{}", self.function) }
+        }
+    }
+}
+
+/// Source text that is not a well-formed program.
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Expected {expected} but found {found} on line {line}")]
+    Unexpected { expected: &'static str, found: String, line: usize },
+
+    #[error("Invalid literal {literal} on line {line}")]
+    InvalidLiteral { literal: String, line: usize },
+
+    #[error("Literal '{literal}' does not fit in {kind} on line {line}")]
+    LiteralOutOfRange { literal: String, kind: &'static str, line: usize },
+
+    #[error("Unterminated {what} on line {line}")]
+    Unterminated { what: &'static str, line: usize },
+}
+
+/// A program that parsed but cannot run, found before execution starts.
+#[derive(Debug, Error)]
+pub enum AnalysisError {
+    #[error("Duplicate identifier '{0}'")]
+    DuplicateIdentifier(Symbol),
+
+    #[error("Duplicate identifier '{name}' in function {function}")]
+    DuplicateLocal { name: Symbol, function: Symbol },
+
+    #[error("Undeclared identifier '{name}' in function {function}")]
+    UndeclaredIdentifier { name: Symbol, function: Symbol },
+
+    #[error("'break' or 'continue' outside of a loop in function {0}")]
+    LoopControlOutsideLoop(Symbol),
+
+    #[error("Function '{function}' expects {expected} arguments but got {found} in function {caller}")]
+    ArityMismatch { function: Symbol, expected: usize, found: usize, caller: Symbol },
+}
+
+#[derive(Debug, Error)]
+pub enum RuntimeError {
+    #[error("{side} operand of '{op}' is {kind}")]
+    InvalidOperand { op: String, side: &'static str, kind: &'static str },
+
+    #[error("Cannot perform '{op}' on {left} and {right}")]
+    UnsupportedOperation { op: String, left: &'static str, right: &'static str },
+
+    #[error("Cannot convert \"{text}\" to {target}")]
+    InvalidNumber { text: String, target: &'static str },
+
+    #[error("Cannot convert {value} to {target}")]
+    InvalidConversion { value: String, target: Type },
+
+    #[error("Format string has {placeholders} placeholders but {arguments} arguments were given")]
+    FormatArguments { placeholders: usize, arguments: usize },
+
+    #[error("Input or output failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Access to '{path}' denied: {reason}")]
+    FileAccessDenied { path: String, reason: &'static str },
+
+    #[error("Cannot access '{path}': {source}")]
+    File { path: String, source: std::io::Error },
+
+    #[error("Input ended before a line could be read")]
+    EndOfInput,
+
+    #[error("Function '{function}' expects {expected} but got {found}")]
+    ArgumentType { function: Symbol, expected: &'static str, found: &'static str },
+
+    #[error("'{function}' is undefined for {value}")]
+    MathDomain { function: Symbol, value: String },
+
+    #[error("Index {index} is out of range for length {length}")]
+    IndexOutOfRange { index: i128, length: usize },
+
+    #[error("Range from {low} to {high} is empty")]
+    EmptyRange { low: i128, high: i128 },
+
+    #[error("Division by zero")]
+    DivisionByZero,
+
+    #[error("Integer overflow in '{op}' of {a} and {b}")]
+    IntegerOverflow { op: String, a: String, b: String },
+
+    #[error("Cannot find function '{0}'")]
+    UndefinedFunction(Symbol),
+
+    #[error("Symbol '{0}' is not a function")]
+    NotAFunction(Symbol),
+
+    #[error("Function '{function}' expects {expected} arguments but got {found}")]
+    ArityMismatch { function: Symbol, expected: usize, found: usize },
+
+    #[error("{0} statement condition must be of type boolean")]
+    NonBooleanCondition(&'static str),
+
+    #[error("Operands of '{op}' must be of type boolean, found {kind}")]
+    NonBooleanOperand { op: String, kind: &'static str },
+
+    /// Wraps an error on its way out of a function, recording one level of the call stack.
+    #[error("{source}")]
+    InFunction { call_site: CallSite, source: Box<RuntimeError> },
+}
+
+impl RuntimeError {
+
+    pub fn in_function(self, function: Symbol, line: Option<usize>) -> RuntimeError {
+        RuntimeError::InFunction {
+            call_site: CallSite { function, line },
+            source: Box::new(self),
+        }
+    }
+
+    /// Call stack at the point of failure, innermost function first.
+    pub fn stack_trace(&self) -> Vec<CallSite> {
+        let mut trace = vec![];
+        let mut error = self;
+        while let RuntimeError::InFunction { call_site, source } = error {
+            trace.push(*call_site);
+            error = source;
+        }
+        trace.reverse();
+        trace
+    }
+}
+
+/// Anything that stops a program from loading or running.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Parse error: {0}")]
+    Parse(#[from] ParseError),
+
+    #[error("Analysis error: {0}")]
+    Analysis(#[from] AnalysisError),
+
+    #[error("Runtime error: {0}")]
+    Runtime(#[from] RuntimeError),
+}
+
+impl Error {
+
+    /// Call stack of a runtime error; empty for errors found before the program ran.
+    pub fn stack_trace(&self) -> Vec<CallSite> {
+        match self {
+            Error::Runtime(error) => { error.stack_trace() }
+            Error::Parse(_) | Error::Analysis(_) => { vec![] }
+        }
+    }
+}
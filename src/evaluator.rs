@@ -1,14 +1,20 @@
-use core::panic;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::fmt::Debug;
 use std::ops::Deref;
 use std::rc::Rc;
+use log::debug;
+use crate::error::RuntimeError;
 use crate::executor::Executor;
-use crate::frame::Frame;
+use crate::frame::{Address, Frame};
+use crate::bigint::BigInt;
+use crate::interner::Symbol;
+use crate::runtime::{Coercion, OverflowMode, Runtime};
 use crate::tree::ExprNode;
-use crate::value::Value;
+use crate::value::{Type, Value};
 
 #[derive(Debug, Clone)]
-enum ArithmeticOp {
+pub enum ArithmeticOp {
     Add,
     Sub,
     Mul,
@@ -16,13 +22,17 @@ enum ArithmeticOp {
 }
 
 #[derive(Debug, Clone)]
-enum RelationalOp {
+pub enum RelationalOp {
     Equal,
     LessThan,
     GreaterThan,
     NotEqual,
     LessThanEqual,
     GreaterThanEqual,
+}
+
+#[derive(Debug, Clone)]
+enum LogicalOp {
     Not,
     And,
     Or
@@ -36,350 +46,443 @@ pub struct Evaluator {
 
 impl Evaluator {
 
-    pub fn evaluate(expr: Rc<ExprNode>, rc_frame: Rc<RefCell<Frame>>) -> Value {
+    pub fn evaluate(expr: Rc<ExprNode>, rc_frame: Rc<RefCell<Frame>>, runtime: &Runtime) -> Result<Value, RuntimeError> {
         match expr.deref() {
-            ExprNode::Var(name) => {
-                rc_frame.borrow().lookup(name)
+            ExprNode::Var(name, address) => {
+                Ok(rc_frame.borrow().lookup(Self::address(*name, address)))
             }
             ExprNode::Val(value) => {
-                value.clone()
+                Ok(value.clone())
             }
             ExprNode::Add(expr_a, expr_b) => {
-                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone());
-                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone());
-                Self::arithmetic(value_a, value_b, ArithmeticOp::Add)
+                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone(), runtime)?;
+                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone(), runtime)?;
+                Self::arithmetic(value_a, value_b, ArithmeticOp::Add, runtime)
             }
             ExprNode::Sub(expr_a, expr_b) => {
-                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone());
-                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone());
-                Self::arithmetic(value_a, value_b, ArithmeticOp::Sub)
+                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone(), runtime)?;
+                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone(), runtime)?;
+                Self::arithmetic(value_a, value_b, ArithmeticOp::Sub, runtime)
             }
             ExprNode::Mul(expr_a, expr_b) => {
-                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone());
-                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone());
-                Self::arithmetic(value_a, value_b, ArithmeticOp::Mul)
+                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone(), runtime)?;
+                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone(), runtime)?;
+                Self::arithmetic(value_a, value_b, ArithmeticOp::Mul, runtime)
             }
             ExprNode::Div(expr_a, expr_b) => {
-                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone());
-                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone());
-                Self::arithmetic(value_a, value_b, ArithmeticOp::Div)
+                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone(), runtime)?;
+                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone(), runtime)?;
+                Self::arithmetic(value_a, value_b, ArithmeticOp::Div, runtime)
             }
             ExprNode::Equal(expr_a, expr_b) => {
-                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone());
-                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone());
-                Self::relational(value_a, value_b, RelationalOp::Equal)
+                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone(), runtime)?;
+                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone(), runtime)?;
+                Self::relational(value_a, value_b, RelationalOp::Equal, runtime)
             }
             ExprNode::LessThan(expr_a, expr_b) => {
-                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone());
-                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone());
-                Self::relational(value_a, value_b, RelationalOp::LessThan)
+                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone(), runtime)?;
+                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone(), runtime)?;
+                Self::relational(value_a, value_b, RelationalOp::LessThan, runtime)
             }
             ExprNode::GreaterThan(expr_a, expr_b) => {
-                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone());
-                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone());
-                Self::relational(value_a, value_b, RelationalOp::GreaterThan)
+                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone(), runtime)?;
+                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone(), runtime)?;
+                Self::relational(value_a, value_b, RelationalOp::GreaterThan, runtime)
             }
             ExprNode::NotEqual(expr_a, expr_b) => {
-                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone());
-                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone());
-                Self::relational(value_a, value_b, RelationalOp::NotEqual)
+                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone(), runtime)?;
+                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone(), runtime)?;
+                Self::relational(value_a, value_b, RelationalOp::NotEqual, runtime)
             }
             ExprNode::LessThanEqual(expr_a, expr_b) => {
-                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone());
-                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone());
-                Self::relational(value_a, value_b, RelationalOp::LessThanEqual)
+                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone(), runtime)?;
+                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone(), runtime)?;
+                Self::relational(value_a, value_b, RelationalOp::LessThanEqual, runtime)
             }
             ExprNode::GreaterThanEqual(expr_a, expr_b) => {
-                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone());
-                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone());
-                Self::relational(value_a, value_b, RelationalOp::GreaterThanEqual)
+                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone(), runtime)?;
+                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone(), runtime)?;
+                Self::relational(value_a, value_b, RelationalOp::GreaterThanEqual, runtime)
             }
             ExprNode::Not(expr_a) => {
-                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone());
-                let value_b: Value = value_a.clone();
-                Self::relational(value_a, value_b, RelationalOp::And)
+                let value_a = Self::condition(expr_a.clone(), rc_frame.clone(), LogicalOp::Not, runtime)?;
+                Ok(Value::Bool(! value_a))
+            }
+            ExprNode::Cast(expr_a, kind) => {
+                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone(), runtime)?;
+                value_a.cast(*kind)
             }
             ExprNode::And(expr_a, expr_b) => {
-                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone());
-                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone());
-                Self::relational(value_a, value_b, RelationalOp::And)
+                // the right operand is only evaluated when the left one is true
+                if Self::condition(expr_a.clone(), rc_frame.clone(), LogicalOp::And, runtime)? {
+                    Ok(Value::Bool(Self::condition(expr_b.clone(), rc_frame.clone(), LogicalOp::And, runtime)?))
+                } else {
+                    Ok(Value::Bool(false))
+                }
             }
             ExprNode::Or(expr_a, expr_b) => {
-                let value_a = Self::evaluate(expr_a.clone(), rc_frame.clone());
-                let value_b = Self::evaluate(expr_b.clone(), rc_frame.clone());
-                Self::relational(value_a, value_b, RelationalOp::Or)
+                // the right operand is only evaluated when the left one is false
+                if Self::condition(expr_a.clone(), rc_frame.clone(), LogicalOp::Or, runtime)? {
+                    Ok(Value::Bool(true))
+                } else {
+                    Ok(Value::Bool(Self::condition(expr_b.clone(), rc_frame.clone(), LogicalOp::Or, runtime)?))
+                }
             }
-            ExprNode::Call(name, rc_exprs) => {
-                println!("[debug] evaluating call '{name}'");
-                match rc_frame.borrow().lookup_global(name) {
+            ExprNode::Call(name, address, rc_exprs, line) => {
+                debug!("evaluating call '{name}'");
+                let callee = rc_frame.borrow().lookup(Self::address(*name, address));
+                match callee {
                     Value::Func(rc_func, argc) => {
-                        
-                        
-                        assert_eq!(argc,rc_exprs.len());
+                        if argc != rc_exprs.len() {
+                            return Err(RuntimeError::ArityMismatch {
+                                function: *name,
+                                expected: argc,
+                                found: rc_exprs.len(),
+                            });
+                        }
 
                         let mut arguments = vec![];
                         for rc_expr in rc_exprs {
-                            let arg = Self::evaluate(rc_expr.clone(), rc_frame.clone());
+                            let arg = Self::evaluate(rc_expr.clone(), rc_frame.clone(), runtime)?;
                             arguments.push(arg);
                         }
 
                         if let Some(globals) = rc_frame.borrow().get_globals() {
-                            Executor::execute_function(rc_func, globals, arguments)
+                            Executor::execute_function(rc_func, globals, arguments, Some(*line), runtime)
                         } else {
                             panic!("Can't find globals in current frame!");
                         }
                     }
+                    Value::Native(native) => {
+                        let mut arguments = vec![];
+                        for rc_expr in rc_exprs {
+                            arguments.push(Self::evaluate(rc_expr.clone(), rc_frame.clone(), runtime)?);
+                        }
+                        native.call(runtime, arguments).map_err(|error| error.in_function(native.name, Some(*line)))
+                    }
+                    Value::Nil => {
+                        Err(RuntimeError::UndefinedFunction(*name))
+                    }
                     _ => {
-                        panic!("Can't find function '{name}' in globals!");
+                        Err(RuntimeError::NotAFunction(*name))
                     }
                 }
             }
         }
     }
 
-    fn arithmetic(value_a: Value, value_b: Value, op : ArithmeticOp) -> Value {
-        match value_a {
-            Value::Nil => { panic!("Left operand of '{op:?}' is Nil!"); }
-            Value::Bool(a) => {panic!(); }
-            Value::I32(a) => {
-                match value_b {
-                    Value::Nil => { panic!("Right operand of '{op:?}' is Nil!"); }
-                    Value::Bool(b) => { panic!("Right operand of '{op:?}' is Bool!"); }
-                    Value::I32(b) => {
-                        match op {
-                            ArithmeticOp::Add => { Value::I32(a + b) }
-                            ArithmeticOp::Sub => { Value::I32(a - b) }
-                            ArithmeticOp::Mul => { Value::I32(a * b) }
-                            ArithmeticOp::Div => { Value::I32(a / b) }
-                        }
-                    }
-                    Value::F32(b) => {
-                        match op {
-                            ArithmeticOp::Add => { Value::F32((a as f32) + b) }
-                            ArithmeticOp::Sub => { Value::F32((a as f32) - b) }
-                            ArithmeticOp::Mul => { Value::F32((a as f32) * b) }
-                            ArithmeticOp::Div => { Value::F32((a as f32) / b) }
-                        }
-                    }
-                    Value::Chars(b) => {
-                        if b.len() <= 1 {
-                            match op {
-                                //IAN: replaced ints with i32 and introduced parseing it from b
-                                ArithmeticOp::Add => { Value::I32(a + (b.parse::<i32>().unwrap())) }
-                                ArithmeticOp::Sub => { Value::I32(a - (b.parse::<i32>().unwrap())) }
-                                ArithmeticOp::Mul => { Value::I32(a * (b.parse::<i32>().unwrap())) }
-                                ArithmeticOp::Div => { Value::I32(a / (b.parse::<i32>().unwrap())) }
-                            }
-                        }
-                        else {
-                            //IAN: fixed some type issues here with int from string shenanigans
-                            match op {
-                                ArithmeticOp::Add => { Value::Chars((a + b.parse::<i32>().unwrap()).to_string()) }
-                                ArithmeticOp::Sub => { Value::Chars((a - b.parse::<i32>().unwrap()).to_string()) }
-                                ArithmeticOp::Mul => { Value::Chars((a * b.parse::<i32>().unwrap()).to_string()) }
-                                ArithmeticOp::Div => { Value::Chars((a / b.parse::<i32>().unwrap()).to_string()) }
-                            }
-                        }
-                    }
-                    Value::Func(_, _) => { panic!("Right operand of '{op:?}' is Func!"); }
+    // frame address filled in by the resolver
+    pub fn address(name: Symbol, address: &Cell<Option<Address>>) -> Address {
+        match address.get() {
+            Some(address) => { address }
+            None => { panic!("Identifier '{name}' was not resolved!"); }
+        }
+    }
+
+    fn invalid_operand(op: &impl Debug, side: &'static str, kind: &'static str) -> RuntimeError {
+        RuntimeError::InvalidOperand { op: format!("{op:?}"), side, kind }
+    }
+
+    fn unsupported(op: &impl Debug, left: &'static str, right: &'static str) -> RuntimeError {
+        RuntimeError::UnsupportedOperation { op: format!("{op:?}"), left, right }
+    }
+
+    // a string used as a number in lenient mode must parse completely
+    fn parse(text: &str) -> Result<Value, RuntimeError> {
+        if let Some(n) = BigInt::parse(text.trim()) {
+            return Ok(Value::from(n));
+        }
+        match text.trim().parse::<f32>() {
+            Ok(n) => { Ok(Value::F32(n)) }
+            Err(_) => { Err(RuntimeError::InvalidNumber { text: text.to_string(), target: "number" }) }
+        }
+    }
+
+    // operand of a logical operator, which must be a boolean
+    fn condition(expr: Rc<ExprNode>, rc_frame: Rc<RefCell<Frame>>, op: LogicalOp, runtime: &Runtime) -> Result<bool, RuntimeError> {
+        match Self::evaluate(expr, rc_frame, runtime)? {
+            Value::Bool(b) => { Ok(b) }
+            value => { Err(RuntimeError::NonBooleanOperand { op: format!("{op:?}"), kind: value.type_name() }) }
+        }
+    }
+
+    // i32 arithmetic; overflow is handled according to the runtime's overflow mode
+    fn integer(a: i32, b: i32, op: &ArithmeticOp, mode: OverflowMode) -> Result<Value, RuntimeError> {
+        let checked = match op {
+            ArithmeticOp::Add => { a.checked_add(b) }
+            ArithmeticOp::Sub => { a.checked_sub(b) }
+            ArithmeticOp::Mul => { a.checked_mul(b) }
+            ArithmeticOp::Div => {
+                if b == 0 {
+                    return Err(RuntimeError::DivisionByZero);
                 }
+                a.checked_div(b)
             }
-            Value::F32(a) => {
-                match value_b {
-                    Value::Nil => { panic!("Right operand of '{op:?}' is Nil!"); }
-                    Value::Bool(b) => { panic!("Right operand of '{op:?}' is Bool!"); }
-                    Value::I32(b) => {
-                        match op {
-                            ArithmeticOp::Add => { Value::F32(a + (b as f32)) }
-                            ArithmeticOp::Sub => { Value::F32(a - (b as f32)) }
-                            ArithmeticOp::Mul => { Value::F32(a * (b as f32)) }
-                            ArithmeticOp::Div => { Value::F32(a / (b as f32)) }
-                        }
-                    }
-                    Value::F32(b) => {
-                        match op {
-                            ArithmeticOp::Add => { Value::F32(a + b) }
-                            ArithmeticOp::Sub => { Value::F32(a - b) }
-                            ArithmeticOp::Mul => { Value::F32(a * b) }
-                            ArithmeticOp::Div => { Value::F32(a / b) }
-                        }
-                    }
-                    Value::Chars(b) => {
-                        if b.len() <= 1 {
-                            match op {
-                                //IAN: changed int to u8 and added parsing it from b
-                                ArithmeticOp::Add => { Value::F32(a + (b.parse::<f32>().unwrap())) }
-                                ArithmeticOp::Sub => { Value::F32(a - (b.parse::<f32>().unwrap())) }
-                                ArithmeticOp::Mul => { Value::F32(a * (b.parse::<f32>().unwrap())) }
-                                ArithmeticOp::Div => { Value::F32(a / (b.parse::<f32>().unwrap())) }
-                            }
-                        }
-                        else {
-                            match op {
-                                ArithmeticOp::Add => { Value::Chars((a + (b.parse::<f32>().unwrap())).to_string()) }
-                                ArithmeticOp::Sub => { Value::Chars((a - (b.parse::<f32>().unwrap())).to_string()) }
-                                ArithmeticOp::Mul => { Value::Chars((a * (b.parse::<f32>().unwrap())).to_string()) }
-                                ArithmeticOp::Div => { Value::Chars((a / (b.parse::<f32>().unwrap())).to_string()) }
-                            }
-                        }
-                    }
-                    Value::Func(_, _) => { panic!("Right operand of '{op:?}' is Func!"); }
+        };
+        if let Some(result) = checked {
+            return Ok(Value::I32(result));
+        }
+        match mode {
+            OverflowMode::Trap => {
+                Err(RuntimeError::IntegerOverflow { op: format!("{op:?}"), a: a.to_string(), b: b.to_string() })
+            }
+            OverflowMode::Wrap => {
+                match op {
+                    ArithmeticOp::Add => { Ok(Value::I32(a.wrapping_add(b))) }
+                    ArithmeticOp::Sub => { Ok(Value::I32(a.wrapping_sub(b))) }
+                    ArithmeticOp::Mul => { Ok(Value::I32(a.wrapping_mul(b))) }
+                    ArithmeticOp::Div => { Ok(Value::I32(a.wrapping_div(b))) }
                 }
             }
-            Value::Chars(a) => {
-                if a.len() <= 1{
-                    match value_b {
-                        Value::Nil => { panic!("Right operand of '{op:?}' is Nil!"); }
-                        Value::Bool(b) => { panic!("Right operand of '{op:?}' is Bool!"); }
-                        Value::I32(b) => {
-                            match op {
-                                //IAN: changed all ints to u8s and introduced parse calls frm a
-                                ArithmeticOp::Add => { Value::Chars(String::from(((a.parse::<i32>().unwrap()) + b).to_string())) }
-                                ArithmeticOp::Sub => { Value::Chars(String::from(((a.parse::<i32>().unwrap()) - b).to_string())) }
-                                ArithmeticOp::Mul => { Value::Chars(String::from(((a.parse::<i32>().unwrap()) * b).to_string())) }
-                                ArithmeticOp::Div => { Value::Chars(String::from(((a.parse::<i32>().unwrap()) / b).to_string())) }
-                            }
-                        }
-                        Value::F32(b) => {
-                            match op {
-                                //IAN: changed int to u8 and added parsing it from a, rust suggested building thr chars as String::from
-                                ArithmeticOp::Add => { Value::Chars(String::from(((a.parse::<f32>().unwrap()) + b).to_string())) }
-                                ArithmeticOp::Sub => { Value::Chars(String::from(((a.parse::<f32>().unwrap()) - b).to_string())) }
-                                ArithmeticOp::Mul => { Value::Chars(String::from(((a.parse::<f32>().unwrap()) * b).to_string())) }
-                                ArithmeticOp::Div => { Value::Chars(String::from(((a.parse::<f32>().unwrap()) / b).to_string())) }
-                            }
-                        }
-                        Value::Chars(b) => {
-                            match op {
-                                //IAN: changed int to u8 and added parsing it from a, rust suggested building thr chars as String::from
-                                ArithmeticOp::Add => { Value::Chars(String::from(((a.parse::<u8>().unwrap()) + (b.parse::<u8>().unwrap())) as char)) }
-                                ArithmeticOp::Sub => { Value::Chars(String::from(((a.parse::<u8>().unwrap()) - (b.parse::<u8>().unwrap())) as char)) }
-                                ArithmeticOp::Mul => { Value::Chars(String::from(((a.parse::<u8>().unwrap()) * (b.parse::<u8>().unwrap())) as char)) }
-                                ArithmeticOp::Div => { Value::Chars(String::from(((a.parse::<u8>().unwrap()) / (b.parse::<u8>().unwrap())) as char)) }
-                            }
-                        }
-                        Value::Func(_, _) => { panic!("Right operand of '{op:?}' is Func!"); }
-                    }
+            OverflowMode::Saturate => {
+                match op {
+                    ArithmeticOp::Add => { Ok(Value::I32(a.saturating_add(b))) }
+                    ArithmeticOp::Sub => { Ok(Value::I32(a.saturating_sub(b))) }
+                    ArithmeticOp::Mul => { Ok(Value::I32(a.saturating_mul(b))) }
+                    ArithmeticOp::Div => { Ok(Value::I32(a.saturating_div(b))) }
                 }
-                else {
-                    match value_b {
-                        Value::Nil => { panic!("Right operand of '{op:?}' is Nil!"); }
-                        Value::Bool(b) => { panic!("Right operand of '{op:?}' is Bool!"); }
-                        Value::I32(b) => {
-                            match op {
-                                ArithmeticOp::Add => { Value::Chars((a.parse::<i32>().unwrap() + b).to_string()) }
-                                ArithmeticOp::Sub => { Value::Chars((a.parse::<i32>().unwrap() - b).to_string()) }
-                                ArithmeticOp::Mul => { Value::Chars((a.parse::<i32>().unwrap() * b).to_string()) }
-                                ArithmeticOp::Div => { Value::Chars((a.parse::<i32>().unwrap() / b).to_string()) }
-                            }
-                        }
-                        Value::F32(b) => {
-                            match op {
-                                //IAN: I'm not sure how well parsing into a float will go, guess we'll see
-                                ArithmeticOp::Add => { Value::Chars((a.parse::<f32>().unwrap() + b).to_string()) }
-                                ArithmeticOp::Sub => { Value::Chars((a.parse::<f32>().unwrap() - b).to_string()) }
-                                ArithmeticOp::Mul => { Value::Chars((a.parse::<f32>().unwrap() * b).to_string()) }
-                                ArithmeticOp::Div => { Value::Chars((a.parse::<f32>().unwrap() / b).to_string()) }
-                            }
-                        }
-                        Value::Chars(b) => {
-                            match op {
-                                //IAN: these should be some kind of literals right?
-                                ArithmeticOp::Add => { Value::Chars(a + &*b) }
-                                _ => {panic!("Cannot perform '{op:?}' on strings"); }
-                            }
-                        }
-                        Value::Func(_, _) => { panic!("Right operand of '{op:?}' is Func!"); }
-                    }
+            }
+            OverflowMode::Promote => {
+                Self::big(&BigInt::from(a), &BigInt::from(b), op)
+            }
+        }
+    }
 
+    // arbitrary-precision arithmetic; results that fit in an i32 become I32 again
+    fn big(a: &BigInt, b: &BigInt, op: &ArithmeticOp) -> Result<Value, RuntimeError> {
+        let result = match op {
+            ArithmeticOp::Add => { a + b }
+            ArithmeticOp::Sub => { a - b }
+            ArithmeticOp::Mul => { a * b }
+            ArithmeticOp::Div => {
+                if b.is_zero() {
+                    return Err(RuntimeError::DivisionByZero);
                 }
-                
+                a / b
+            }
+        };
+        Ok(Value::from(result))
+    }
+
+    // fixed-width integer arithmetic done in i128, then narrowed to `kind` by the overflow mode
+    fn sized(a: i128, b: i128, kind: Type, op: &ArithmeticOp, mode: OverflowMode) -> Result<Value, RuntimeError> {
+        let checked = match op {
+            ArithmeticOp::Add => { a.checked_add(b) }
+            ArithmeticOp::Sub => { a.checked_sub(b) }
+            ArithmeticOp::Mul => { a.checked_mul(b) }
+            ArithmeticOp::Div => {
+                if b == 0 {
+                    return Err(RuntimeError::DivisionByZero);
+                }
+                a.checked_div(b)
+            }
+        };
+        if let Some(value) = checked.and_then(|n| Value::from_i128(n, kind)) {
+            return Ok(value);
+        }
+        match mode {
+            // only i32 grows into a BigInt; a declared width keeps its type, so it traps
+            OverflowMode::Promote if kind == Type::I32 => {
+                Self::big(&BigInt::from(a), &BigInt::from(b), op)
+            }
+            OverflowMode::Trap | OverflowMode::Promote => {
+                Err(RuntimeError::IntegerOverflow { op: format!("{op:?}"), a: a.to_string(), b: b.to_string() })
+            }
+            OverflowMode::Wrap => {
+                let n = match op {
+                    ArithmeticOp::Add => { a.wrapping_add(b) }
+                    ArithmeticOp::Sub => { a.wrapping_sub(b) }
+                    ArithmeticOp::Mul => { a.wrapping_mul(b) }
+                    ArithmeticOp::Div => { a.wrapping_div(b) }
+                };
+                let value = match kind {
+                    Type::I64 => { Value::I64(n as i64) }
+                    Type::U8 => { Value::U8(n as u8) }
+                    Type::U32 => { Value::U32(n as u32) }
+                    Type::U64 => { Value::U64(n as u64) }
+                    _ => { Value::I32(n as i32) }
+                };
+                Ok(value)
+            }
+            OverflowMode::Saturate => {
+                // i128 itself only overflows on the product of two huge 64-bit operands
+                let n = checked.unwrap_or(if (a < 0) != (b < 0) { i128::MIN } else { i128::MAX });
+                let (min, max) = kind.range().unwrap();
+                Ok(Value::from_i128(n.clamp(min, max), kind).unwrap())
             }
-            Value::Func(_, _) => { panic!("Left operand of '{op:?}' is Func!"); }
         }
     }
 
-    fn relational(value_a: Value, value_b: Value, op : RelationalOp) -> Value {
-        match value_a {
-            Value::Nil => { panic!("Left operand of '{op:?}' is Nil!"); }
-            Value::Bool(a) => {
-                match value_b {
-                    Value::Bool(b) => {
-                        match op {
-                            RelationalOp::And => { Value::Bool(a & b) }
-                            RelationalOp::Or => { Value::Bool(a | b) }
-                            RelationalOp::Not => { Value::Bool(! a) }
-                            _ => { panic!(); }
-                        }
-                    }
-                    _ => { panic!(); }
+    // numbers only; nil, bools, lists and functions are invalid operands, chars and strings unsupported ones
+    fn numbers(value_a: &Value, value_b: &Value, op: &impl Debug) -> Result<(), RuntimeError> {
+        for (value, side) in [(value_a, "Left"), (value_b, "Right")] {
+            match value {
+                Value::Nil | Value::Bool(_) | Value::List(_) | Value::Func(_, _) | Value::Native(_) => {
+                    return Err(Self::invalid_operand(op, side, value.type_name()));
                 }
+                Value::Char(_) | Value::Str(_) => {
+                    return Err(Self::unsupported(op, value_a.type_name(), value_b.type_name()));
+                }
+                _ => {}
             }
-            Value::I32(a) => {
-                match value_b {
-                    Value::Nil => { panic!("Right operand of '{op:?}' is Nil!"); }
-                    Value::Bool(b) => { panic!("Right operand of '{op:?}' is Bool!"); }
-                    Value::I32(b) => {
-                        match op {
-                            RelationalOp::Equal => { Value::Bool(a == b) }
-                            RelationalOp::LessThan => { Value::Bool(a < b) }
-                            RelationalOp::GreaterThan => { Value::Bool(a > b) }
-                            RelationalOp::NotEqual => { Value::Bool(a != b) }
-                            RelationalOp::LessThanEqual => { Value::Bool(a <= b) }
-                            RelationalOp::GreaterThanEqual => { Value::Bool(a >= b) }
-                            _ => { panic!(); }
-                        }
-                    }
-                    Value::F32(b) => {
-                        match op {
-                            //IAN: Added safecasting because Rust was complaining
-                            RelationalOp::Equal => { Value::Bool((a as f32) == b) }
-                            RelationalOp::LessThan => { Value::Bool(a < b as i32) }
-                            RelationalOp::GreaterThan => { Value::Bool(a > b as i32) }
-                            RelationalOp::NotEqual => { Value::Bool((a as f32) != b) }
-                            RelationalOp::LessThanEqual => { Value::Bool(a <= b as i32) }
-                            RelationalOp::GreaterThanEqual => { Value::Bool(a >= b as i32) }
-                            _ => { panic!(); }
-                        }
-                    }
-                    Value::Chars(_) => { todo!() }
-                    Value::Func(_, _) => { panic!("Right operand of '{op:?}' is Func!"); }
+        }
+        Ok(())
+    }
+
+    // any integer as a BigInt
+    fn bigint(value: &Value) -> BigInt {
+        match value {
+            Value::BigInt(n) => { n.clone() }
+            _ => { BigInt::from(value.to_i128().unwrap()) }
+        }
+    }
+
+    // arithmetic once an i64, u8, u32, u64 or f64 is involved; both sides are brought
+    // to their common type (see `Type::common`), a BigInt counts as the widest integer
+    fn numeric(value_a: Value, value_b: Value, op: ArithmeticOp, runtime: &Runtime) -> Result<Value, RuntimeError> {
+        Self::numbers(&value_a, &value_b, &op)?;
+        let kind = match (value_a.kind(), value_b.kind()) {
+            (Some(a), Some(b)) => { Type::common(a, b) }
+            (Some(kind), None) | (None, Some(kind)) if kind.is_float() => { kind }
+            _ => { return Self::big(&Self::bigint(&value_a), &Self::bigint(&value_b), &op); }
+        };
+        if kind.is_float() {
+            let (a, b) = (value_a.to_f64().unwrap(), value_b.to_f64().unwrap());
+            let result = match op {
+                ArithmeticOp::Add => { a + b }
+                ArithmeticOp::Sub => { a - b }
+                ArithmeticOp::Mul => { a * b }
+                ArithmeticOp::Div => { a / b }
+            };
+            return Ok(if kind == Type::F64 { Value::F64(result) } else { Value::F32(result as f32) });
+        }
+        Self::sized(value_a.to_i128().unwrap(), value_b.to_i128().unwrap(), kind, &op, runtime.overflow)
+    }
+
+    // comparison once an i64, u8, u32, u64 or f64 is involved
+    fn ordered(value_a: Value, value_b: Value, op: RelationalOp) -> Result<Value, RuntimeError> {
+        Self::numbers(&value_a, &value_b, &op)?;
+        let float = |value: &Value| value.kind().is_some_and(Type::is_float);
+        let ordering = if float(&value_a) || float(&value_b) {
+            value_a.to_f64().unwrap().partial_cmp(&value_b.to_f64().unwrap())
+        } else {
+            Self::bigint(&value_a).partial_cmp(&Self::bigint(&value_b))
+        };
+        Ok(Self::compare(ordering, op))
+    }
+
+    /// The coercion table: how the operands of an arithmetic or relational operator meet.
+    ///
+    /// | left \ right | number             | char                | string              | bool         |
+    /// |--------------|--------------------|---------------------|---------------------|--------------|
+    /// | number       | common number type | `+` moves the char  | strict: error       | error        |
+    /// |              |                    |                     | lenient: parsed     |              |
+    /// | char         | `+`, `-` move it   | `-` gives distance, | error               | error        |
+    /// |              |                    | compared by code    |                     |              |
+    /// | string       | strict: error      | error               | `+` concatenates,   | error        |
+    /// |              | lenient: parsed    |                     | lexicographic order |              |
+    /// | bool         | error              | error               | error               | `==`, `!=`   |
+    ///
+    /// Nil and functions are never operands. Two numbers are brought to their common type
+    /// (see `Type::common`), with a BigInt counting as the widest integer. In lenient mode
+    /// a string meeting a number is parsed as an integer, or else as an `f32`, and the
+    /// operation goes on between numbers.
+    fn coerce(value_a: Value, value_b: Value, op: &impl Debug, runtime: &Runtime) -> Result<(Value, Value), RuntimeError> {
+        let number = |value: &Value| value.to_f64().is_some();
+        match (&value_a, &value_b) {
+            (Value::Str(text), other) | (other, Value::Str(text)) if number(other) => {
+                if runtime.coercion == Coercion::Strict {
+                    return Err(Self::unsupported(op, value_a.type_name(), value_b.type_name()));
+                }
+                let parsed = Self::parse(text)?;
+                match value_a {
+                    Value::Str(_) => { Ok((parsed, value_b)) }
+                    _ => { Ok((value_a, parsed)) }
                 }
             }
-            Value::F32(a) => {
-                match value_b {
-                    Value::Nil => { panic!("Right operand of '{op:?}' is Nil!"); }
-                    Value::Bool(b) => { panic!("Right operand of '{op:?}' is Bool!"); }
-                    Value::I32(b) => {
-                        match op {
-                            //IAN: safe casted the ints to floats
-                            RelationalOp::Equal => { Value::Bool(a == (b as f32)) }
-                            RelationalOp::LessThan => { Value::Bool(a < b as f32) }
-                            RelationalOp::GreaterThan => { Value::Bool(a > b as f32) }
-                            RelationalOp::NotEqual => { Value::Bool(a != (b as f32)) }
-                            RelationalOp::LessThanEqual => { Value::Bool(a <= b as f32) }
-                            RelationalOp::GreaterThanEqual => { Value::Bool(a >= b as f32) }
-                            _ => { panic!(); }
-                        }
-                    }
-                    Value::F32(b) => {
-                        match op {
-                            RelationalOp::Equal => { Value::Bool(a == b) }
-                            RelationalOp::LessThan => { Value::Bool(a < b) }
-                            RelationalOp::GreaterThan => { Value::Bool(a > b) }
-                            RelationalOp::NotEqual => { Value::Bool(a != b) }
-                            RelationalOp::LessThanEqual => { Value::Bool(a <= b) }
-                            RelationalOp::GreaterThanEqual => { Value::Bool(a >= b) }
-                            _ => { panic!(); }
-                        }
-                    }
-                    Value::Chars(_) => { todo!() }
-                    Value::Func(_, _) => { panic!("Right operand of '{op:?}' is Func!"); }
+            _ => { Ok((value_a, value_b)) }
+        }
+    }
+
+    pub fn arithmetic(value_a: Value, value_b: Value, op : ArithmeticOp, runtime: &Runtime) -> Result<Value, RuntimeError> {
+        // i32 on both sides is by far the most common case
+        if let (Value::I32(a), Value::I32(b)) = (&value_a, &value_b) {
+            return Self::integer(*a, *b, &op, runtime.overflow);
+        }
+        match Self::coerce(value_a, value_b, &op, runtime)? {
+            (Value::Str(a), Value::Str(b)) => {
+                match op {
+                    ArithmeticOp::Add => { Ok(Value::Str(a + &b)) }
+                    _ => { Err(Self::unsupported(&op, "Str", "Str")) }
+                }
+            }
+            (Value::Char(a), Value::Char(b)) => {
+                match op {
+                    ArithmeticOp::Sub => { Ok(Value::I32(a as i32 - b as i32)) }
+                    _ => { Err(Self::unsupported(&op, "Char", "Char")) }
                 }
             }
-            Value::Chars(_) => { todo!() }
-            Value::Func(_, _) => { panic!("Left operand of '{op:?}' is Func!"); }
+            (Value::Char(c), value_b) if value_b.to_i128().is_some() => {
+                match op {
+                    ArithmeticOp::Add => { Self::shift(c, value_b.to_i128().unwrap()) }
+                    ArithmeticOp::Sub => { Self::shift(c, -value_b.to_i128().unwrap()) }
+                    _ => { Err(Self::unsupported(&op, "Char", value_b.type_name())) }
+                }
+            }
+            (value_a, Value::Char(c)) if value_a.to_i128().is_some() => {
+                match op {
+                    ArithmeticOp::Add => { Self::shift(c, value_a.to_i128().unwrap()) }
+                    _ => { Err(Self::unsupported(&op, value_a.type_name(), "Char")) }
+                }
+            }
+            (value_a, value_b) => { Self::numeric(value_a, value_b, op, runtime) }
         }
     }
 
+    // the char `offset` code points away from `c`
+    fn shift(c: char, offset: i128) -> Result<Value, RuntimeError> {
+        let code = c as i128 + offset;
+        u32::try_from(code).ok()
+            .and_then(char::from_u32)
+            .map(Value::Char)
+            .ok_or(RuntimeError::InvalidConversion { value: code.to_string(), target: Type::Char })
+    }
+
+    pub fn relational(value_a: Value, value_b: Value, op : RelationalOp, runtime: &Runtime) -> Result<Value, RuntimeError> {
+        if let (Value::I32(a), Value::I32(b)) = (&value_a, &value_b) {
+            return Ok(Self::compare(a.partial_cmp(b), op));
+        }
+        match Self::coerce(value_a, value_b, &op, runtime)? {
+            (Value::Bool(a), Value::Bool(b)) => {
+                match op {
+                    RelationalOp::Equal | RelationalOp::NotEqual => { Ok(Self::compare(a.partial_cmp(&b), op)) }
+                    _ => { Err(Self::unsupported(&op, "Bool", "Bool")) }
+                }
+            }
+            (Value::Bool(_), value_b) => { Err(Self::unsupported(&op, "Bool", value_b.type_name())) }
+            (Value::Char(a), Value::Char(b)) => { Ok(Self::compare(a.partial_cmp(&b), op)) }
+            // lexicographic, by code point
+            (Value::Str(a), Value::Str(b)) => { Ok(Self::compare(a.partial_cmp(&b), op)) }
+            (value_a, value_b) => { Self::ordered(value_a, value_b, op) }
+        }
+    }
+
+    // relational result from an ordering; unordered operands (NaN) are only unequal
+    fn compare(ordering: Option<Ordering>, op: RelationalOp) -> Value {
+        let result = match ordering {
+            None => { matches!(op, RelationalOp::NotEqual) }
+            Some(ordering) => {
+                match op {
+                    RelationalOp::Equal => { ordering == Ordering::Equal }
+                    RelationalOp::LessThan => { ordering == Ordering::Less }
+                    RelationalOp::GreaterThan => { ordering == Ordering::Greater }
+                    RelationalOp::NotEqual => { ordering != Ordering::Equal }
+                    RelationalOp::LessThanEqual => { ordering != Ordering::Greater }
+                    RelationalOp::GreaterThanEqual => { ordering != Ordering::Less }
+                }
+            }
+        };
+        Value::Bool(result)
+    }
+
 }
\ No newline at end of file
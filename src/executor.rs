@@ -1,11 +1,15 @@
 use std::cell::RefCell;
 use std::ops::Deref;
 use std::rc::Rc;
+use log::{debug, info};
 use tui::symbols::block;
 
+use crate::error::RuntimeError;
 use crate::evaluator::Evaluator;
-use crate::frame::Frame;
-use crate::tree::{BlockNode, FuncNode, ProgramNode, StmtNode};
+use crate::frame::{Address, Frame};
+use crate::interner::Symbol;
+use crate::runtime::Runtime;
+use crate::tree::{BlockNode, FuncNode, Parameter, ProgramNode, StmtNode};
 use crate::value::Value;
 
 enum Control {
@@ -17,167 +21,274 @@ enum Control {
 
 pub struct Executor {
     program: Rc<ProgramNode>,
+    runtime: Rc<Runtime>,
+    globals: Rc<RefCell<Frame>>,
 }
 
 impl Executor {
 
-    pub fn new(program: Rc<ProgramNode>) -> Executor {
-        Executor { program }
+    /// Sets up the global frame; the program must already be analyzed and resolved.
+    pub fn new(program: Rc<ProgramNode>, runtime: Rc<Runtime>) -> Executor {
+        let mut global = Frame::new(None, program.symbols.borrow().map.len());
+        global.init_symbols(program.symbols.borrow().deref());
+        Executor { program, runtime, globals: Rc::new(RefCell::new(global)) }
     }
 
-    pub fn execute(&self) {
-        println!("[info] Execute.");
-        self.execute_program();
+    /// Calls the entry function, usually `main`, with the program arguments:
+    ///
+    /// - `func main()` gets nothing, though `args()` still returns the arguments;
+    /// - `func main(args)` gets the list of all of them;
+    /// - any other `main` gets one argument per parameter, cast to the parameter's
+    ///   declared type, and the counts must match.
+    pub fn execute(&self, entry: Symbol) -> Result<Value, RuntimeError> {
+        let arguments = match self.function(entry)? {
+            Value::Func(rc_entry, _) => {
+                Self::entry_arguments(entry, &rc_entry.parameters, self.runtime.arguments())
+                    .map_err(|error| error.in_function(entry, None))?
+            }
+            _ => { vec![] }
+        };
+        self.call(entry, arguments)
     }
 
-    fn execute_program(&self) {
-        println!("[info] Execute Program.");
-
-        // get program node symbol table
-        let rc_symbols = self.program.symbols.clone();
-        let symbols = rc_symbols.borrow();
-
-        // find main function node
-        let rc_main = if let Some(main) = symbols.map.get("main") {
-            match &main.value {
-                Value::Func(rc_main, _) => { rc_main.clone() }
-                _ => { panic!("Symbol 'main' is not a function!"); }
+    fn entry_arguments(entry: Symbol, parameters: &[Parameter], arguments: &[String]) -> Result<Vec<Value>, RuntimeError> {
+        let texts = arguments.iter().map(|argument| Value::Str(argument.clone()));
+        match parameters {
+            [] => { Ok(vec![]) }
+            [parameter] if parameter.kind.is_none() => { Ok(vec![Value::List(Rc::new(texts.collect()))]) }
+            _ if parameters.len() != arguments.len() => {
+                Err(RuntimeError::ArityMismatch {
+                    function: entry,
+                    expected: parameters.len(),
+                    found: arguments.len(),
+                })
             }
-        } else {
-            panic!("Cannot find 'main' symbol!");
-        };
+            _ => {
+                parameters.iter().zip(texts)
+                    .map(|(parameter, text)| {
+                        match parameter.kind {
+                            Some(kind) => { text.cast(kind) }
+                            None => { Ok(text) }
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
 
-        // create global stack frame
-        let mut global = Frame::new(None);
-        global.init_symbols(symbols.deref());
-        let rc_global = Rc::new(RefCell::new(global));
+    // the value a top-level name is bound to
+    fn function(&self, name: Symbol) -> Result<Value, RuntimeError> {
+        let slot = self.program.symbols.borrow().map.get(&name).map(|entry| entry.slot);
+        match slot {
+            Some(slot) => { Ok(self.globals.borrow().lookup(Address::new(0, slot))) }
+            None => { Err(RuntimeError::UndefinedFunction(name)) }
+        }
+    }
 
-        // execute main function
-        let arguments = vec![];
-        Self::execute_function(rc_main, rc_global, arguments);
+    /// Calls a top-level function by name.
+    pub fn call(&self, name: Symbol, arguments: Vec<Value>) -> Result<Value, RuntimeError> {
+        info!("Execute '{name}'.");
+        match self.function(name)? {
+            Value::Func(rc_func, _) => {
+                Self::execute_function(rc_func, self.globals.clone(), arguments, None, &self.runtime)
+            }
+            Value::Native(native) => {
+                native.call(&self.runtime, arguments).map_err(|error| error.in_function(name, None))
+            }
+            _ => { Err(RuntimeError::NotAFunction(name)) }
+        }
     }
 
+    // `line` is the call site, or None for the entry function
     pub fn execute_function(
         rc_func: Rc<FuncNode>,
         globals: Rc<RefCell<Frame>>,
-        arguments: Vec<Value>
-    ) -> Value
+        arguments: Vec<Value>,
+        line: Option<usize>,
+        runtime: &Runtime,
+    ) -> Result<Value, RuntimeError>
     {
         let name = &rc_func.name;
-        println!("[debug] calling function '{name}'.");
+        debug!("calling function '{name}'.");
 
-        // create local stack frame
-        let mut locals = Frame::new(Some(globals));
+        // create local stack frame with a slot for every parameter and let
+        let frame_size = rc_func.block_node.symbols.borrow().map.len();
+        let mut locals = Frame::new(Some(globals), frame_size);
 
         // initialize parameters
-        let name = &rc_func.name;
-        if rc_func.numParameters() > arguments.len() {
-            panic!("Not enough arguments for function {name}!");
+        if rc_func.numParameters() != arguments.len() {
+            let error = RuntimeError::ArityMismatch {
+                function: rc_func.name,
+                expected: rc_func.numParameters(),
+                found: arguments.len(),
+            };
+            return Err(error.in_function(rc_func.name, line));
         }
-        if rc_func.numParameters() < arguments.len() {
-            panic!("To many arguments for function {name}!");
+        // typed parameters choose the width of their argument
+        let mut converted = Vec::with_capacity(arguments.len());
+        for (parameter, argument) in rc_func.parameters.iter().zip(arguments) {
+            let argument = match parameter.kind {
+                Some(kind) => { argument.convert(kind) }
+                None => { Ok(argument) }
+            };
+            match argument {
+                Ok(argument) => { converted.push(argument) }
+                Err(error) => { return Err(error.in_function(rc_func.name, line)); }
+            }
         }
-        locals.init_parameters(&rc_func.parameters, arguments);
+        locals.init_parameters(&rc_func.parameters, converted);
 
         // execute function block
         let rc_block = rc_func.block_node.clone();
         let rc_locals = Rc::new(RefCell::new(locals));
-        let (_, value) = Self::execute_block_without_scope(rc_block, rc_locals);
-
-        value
+        match Self::execute_block_without_scope(rc_block, rc_locals, runtime) {
+            Ok((_, value)) => { Ok(value) }
+            Err(error) => { Err(error.in_function(rc_func.name, line)) }
+        }
     }
 
     fn execute_block_without_scope(
         rc_block: Rc<BlockNode>,
         rc_locals: Rc<RefCell<Frame>>,
-    ) -> (Control, Value) {
+        runtime: &Runtime,
+    ) -> Result<(Control, Value), RuntimeError> {
 
         // execute statements
         for statement in &rc_block.statements {
             let (control, value) = Self::execute_statement(
                 statement.clone(),
                 rc_locals.clone(),
-            );
+                runtime,
+            )?;
+            // anything but Next ends the block and is handed to the enclosing statement
             match control {
                 Control::Next => {}
-                Control::Return => { return (Control::Return, value) }
-                Control::Break => {}
-                Control::Continue => {}
+                Control::Return | Control::Break | Control::Continue => { return Ok((control, value)) }
             }
         }
 
-        (Control::Next, Value::Nil)
+        Ok((Control::Next, Value::Nil))
     }
 
     fn execute_statement(
         rc_statement: Rc<StmtNode>,
         rc_locals: Rc<RefCell<Frame>>,
-    ) -> (Control, Value)
+        runtime: &Runtime,
+    ) -> Result<(Control, Value), RuntimeError>
     {
         match rc_statement.deref() {
             StmtNode::Let(_) => {
-                println!("[debug] ignoring let statement");
-                (Control::Next, Value::Nil)
+                debug!("ignoring let statement");
+                Ok((Control::Next, Value::Nil))
             }
             StmtNode::Assign(assign) => {
-                println!("[debug] executing assign statement");
-                let name = &assign.name;
-                let value = Evaluator::evaluate(assign.expr.clone(), rc_locals.clone());
-                rc_locals.borrow_mut().assign(name, value);
-                (Control::Next, Value::Nil)
+                debug!("executing assign statement");
+                let address = Evaluator::address(assign.name, &assign.address);
+                let mut value = Evaluator::evaluate(assign.expr.clone(), rc_locals.clone(), runtime)?;
+                if let Some(kind) = assign.kind.get() {
+                    value = value.convert(kind)?;
+                }
+                rc_locals.borrow_mut().assign(address, value);
+                Ok((Control::Next, Value::Nil))
             }
             StmtNode::If(ifNode) => {               
-                println!("[debug] executing if statement");
+                debug!("executing if statement");
                 let value_cond = Evaluator::evaluate(
-                    ifNode.cond.clone(), rc_locals.clone());
+                    ifNode.cond.clone(), rc_locals.clone(), runtime)?;
                 if let Value::Bool(b) = value_cond {
                     if b {
                         Self::execute_block_without_scope(
-                            ifNode.block_node_true.clone(), rc_locals.clone())
+                            ifNode.block_node_true.clone(), rc_locals.clone(), runtime)
                     } else {
                         Self::execute_block_without_scope(
-                            ifNode.block_node_false.clone(), rc_locals.clone())
+                            ifNode.block_node_false.clone(), rc_locals.clone(), runtime)
                     }
                 } else {
-                    panic!("If-then-else statement condition must be of type boolean!");
+                    Err(RuntimeError::NonBooleanCondition("If-then-else"))
                 }
             }
             StmtNode::While(whileNode) => {
-                println!("[debug] executing while statement");
-                let mut value_cond = Evaluator::evaluate(
-                    whileNode.cond.clone(), rc_locals.clone());
-                let mut output = (Control::Break, Value::Bool(true));
-
-                if let Value::Bool(mut b) = value_cond{
-                    while b {
-                        output = Self::execute_block_without_scope(
-                            whileNode.block_node_true.clone(), rc_locals.clone());
-
-                        value_cond = Evaluator::evaluate(
-                            whileNode.cond.clone(), rc_locals.clone());
+                debug!("executing while statement");
+                loop {
+                    let value_cond = Evaluator::evaluate(
+                        whileNode.cond.clone(), rc_locals.clone(), runtime)?;
+                    match value_cond {
+                        Value::Bool(true) => {}
+                        Value::Bool(false) => { return Ok((Control::Next, Value::Nil)); }
+                        _ => { return Err(RuntimeError::NonBooleanCondition("While")); }
+                    }
 
-                        b = value_cond.unwrap();
+                    let (control, value) = Self::execute_block_without_scope(
+                        whileNode.block_node_true.clone(), rc_locals.clone(), runtime)?;
+                    match control {
+                        Control::Next | Control::Continue => {}
+                        Control::Break => { return Ok((Control::Next, Value::Nil)); }
+                        Control::Return => { return Ok((Control::Return, value)); }
                     }
-                    
-                    return output;
-                } else {
-                    panic!("While statement condition must be of type boolean!");
                 }
             }
             StmtNode::Return(ret) => {
-                println!("[debug] executing return statement");
-                let value = Evaluator::evaluate(ret.expr.clone(), rc_locals.clone());
-                (Control::Return, value)
+                debug!("executing return statement");
+                let value = Evaluator::evaluate(ret.expr.clone(), rc_locals.clone(), runtime)?;
+                Ok((Control::Return, value))
+            }
+            StmtNode::Break => {
+                debug!("executing break statement");
+                Ok((Control::Break, Value::Nil))
+            }
+            StmtNode::Continue => {
+                debug!("executing continue statement");
+                Ok((Control::Continue, Value::Nil))
             }
             StmtNode::Print(print) => {
-                println!("[debug] executing print statement");
-                let value = Evaluator::evaluate(print.expr.clone(), rc_locals.clone());
-                value.print();
-                (Control::Next, Value::Nil)
+                debug!("executing print statement");
+                let mut values = Vec::with_capacity(print.exprs.len());
+                for rc_expr in &print.exprs {
+                    values.push(Evaluator::evaluate(rc_expr.clone(), rc_locals.clone(), runtime)?);
+                }
+                runtime.print(&Self::format(values, print.template)?)?;
+                Ok((Control::Next, Value::Nil))
             }
 
         }
 
     }
 
-}
\ No newline at end of file
+    /// Text of a `print` statement. When it is a `template`, each `{}` placeholder in
+    /// the first argument is replaced by the next argument (`{{` and `}}` give
+    /// literal braces). Otherwise the arguments are separated by spaces.
+    fn format(values: Vec<Value>, template: bool) -> Result<String, RuntimeError> {
+        let template = match values.first() {
+            Some(Value::Str(text)) if template => { text.clone() }
+            _ => {
+                let texts: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+                return Ok(texts.join(" "));
+            }
+        };
+
+        let mut arguments = values.iter().skip(1);
+        let mut output = String::new();
+        let mut placeholders = 0;
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, chars.peek()) {
+                ('{', Some('{')) | ('}', Some('}')) => {
+                    chars.next();
+                    output.push(c);
+                }
+                ('{', Some('}')) => {
+                    chars.next();
+                    placeholders += 1;
+                    if let Some(argument) = arguments.next() {
+                        output.push_str(&argument.to_string());
+                    }
+                }
+                _ => { output.push(c); }
+            }
+        }
+        if placeholders != values.len() - 1 {
+            return Err(RuntimeError::FormatArguments { placeholders, arguments: values.len() - 1 });
+        }
+        Ok(output)
+    }
+}
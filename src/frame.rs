@@ -1,21 +1,33 @@
 use std::cell::RefCell;
-use std::collections::HashMap;
 use std::rc::Rc;
 use crate::symbols::Symbols;
 use crate::tree::Parameter;
 use crate::value::Value;
 
+/// Resolved location of a variable: `depth` frames up from the current one, at index `slot`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Address {
+    pub depth: usize,
+    pub slot: usize,
+}
+
+impl Address {
+    pub fn new(depth: usize, slot: usize) -> Address {
+        Address { depth, slot }
+    }
+}
+
 pub struct Frame {
     globals: Option<Rc<RefCell<Frame>>>,
-    values: HashMap<String, Value>,
+    values: Vec<Value>,
 }
 
 impl Frame {
 
-    pub fn new(global: Option<Rc<RefCell<Frame>>>) -> Frame {
+    pub fn new(global: Option<Rc<RefCell<Frame>>>, size: usize) -> Frame {
         Frame {
             globals: global,
-            values: HashMap::new(),
+            values: vec![Value::Nil; size],
         }
     }
 
@@ -24,46 +36,44 @@ impl Frame {
     }
 
     pub fn init_symbols(&mut self, symbols: &Symbols) {
-        for (name, symbol) in &symbols.map {
-            self.values.insert(name.clone(), symbol.value.clone());
+        self.values.resize(symbols.map.len(), Value::Nil);
+        for symbol in symbols.map.values() {
+            self.values[symbol.slot] = symbol.value.clone();
         }
     }
 
-    pub fn init_parameters(&mut self, parameters: &Vec<Parameter>, arguments: Vec<Value>) {
+    pub fn init_parameters(&mut self, parameters: &[Parameter], arguments: Vec<Value>) {
         assert_eq!(parameters.len(), arguments.len());
 
-        let mut iter_args = arguments.into_iter();
-
-        for rc_param in parameters {
-            let name = rc_param.name.clone();
-            let arg = iter_args.next().unwrap();
-            self.values.insert(name, arg);
+        // parameters occupy the first slots of a function frame
+        for (slot, arg) in arguments.into_iter().enumerate() {
+            self.values[slot] = arg;
         }
     }
 
-    pub fn assign(&mut self, name: &String, value: Value) {
-        self.values.insert(name.clone(), value);
-    }
-
-    pub fn lookup(&self,  name: &String) -> Value {
-        match self.values.get(name) {
-            None => { Value::Nil }
-            Some(value) => { value.clone() }
-        }
-    }
-
-    pub fn lookup_global(&self,  name: &String) -> Value {
-        match &self.globals {
-            None => { Value::Nil }
-            Some(rc_globals) => {
-                rc_globals.borrow().lookup(name)
+    pub fn assign(&mut self, address: Address, value: Value) {
+        if address.depth == 0 {
+            self.values[address.slot] = value;
+        } else {
+            match &self.globals {
+                None => { panic!("Frame depth {} is out of range!", address.depth) }
+                Some(rc_globals) => {
+                    rc_globals.borrow_mut().assign(Address::new(address.depth - 1, address.slot), value)
+                }
             }
         }
     }
 
-    pub fn print(& self) {
-        for (name, value) in &self.values {
-            println!("    {name} = {value:?}");
+    pub fn lookup(&self, address: Address) -> Value {
+        if address.depth == 0 {
+            self.values[address.slot].clone()
+        } else {
+            match &self.globals {
+                None => { panic!("Frame depth {} is out of range!", address.depth) }
+                Some(rc_globals) => {
+                    rc_globals.borrow().lookup(Address::new(address.depth - 1, address.slot))
+                }
+            }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+
+/// Interned identifier. Two symbols are equal exactly when their names are equal,
+/// so lookups compare and hash a `u32` instead of a `String`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+struct Interner {
+    names: Vec<&'static str>,
+    symbols: HashMap<&'static str, Symbol>,
+}
+
+impl Interner {
+
+    fn new() -> Interner {
+        Interner {
+            names: vec![],
+            symbols: HashMap::new(),
+        }
+    }
+
+    fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(name) {
+            return *symbol;
+        }
+        // names live for the whole run, so each distinct one is leaked once
+        let name: &'static str = name.to_string().leak();
+        let symbol = Symbol(self.names.len() as u32);
+        self.names.push(name);
+        self.symbols.insert(name, symbol);
+        symbol
+    }
+
+    fn name(&self, symbol: Symbol) -> &'static str {
+        self.names[symbol.0 as usize]
+    }
+}
+
+// one interner for the whole process, so symbols (and the errors holding them)
+// can be formatted on any thread
+static INTERNER: OnceLock<Mutex<Interner>> = OnceLock::new();
+
+fn interner() -> &'static Mutex<Interner> {
+    INTERNER.get_or_init(|| Mutex::new(Interner::new()))
+}
+
+impl Symbol {
+
+    pub fn intern(name: &str) -> Symbol {
+        // the interner is never left half-updated, so a poisoned lock is still usable
+        interner().lock().unwrap_or_else(|poisoned| poisoned.into_inner()).intern(name)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        interner().lock().unwrap_or_else(|poisoned| poisoned.into_inner()).name(self)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// debug output (parse trees, frames) shows the name rather than the id
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
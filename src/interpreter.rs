@@ -0,0 +1,60 @@
+use crate::error::Error;
+use crate::lexer::Lexer;
+use crate::machine::Machine;
+use crate::parser::DescentParser;
+use crate::runtime::Runtime;
+
+/// Entry point for embedding the language.
+///
+/// ```
+/// use PL_Final::{Interpreter, Value};
+///
+/// let source = "func square(n) [ return n * n; ] func main() [ ]";
+/// let machine = Interpreter::new().load_source(source)?;
+/// assert_eq!(machine.call("square", vec![Value::I32(12)])?.to_string(), "144");
+/// # Ok::<(), PL_Final::Error>(())
+/// ```
+pub struct Interpreter {
+    runtime: Runtime,
+}
+
+impl Interpreter {
+
+    /// Prints to stdout and reports errors to stderr, with default arithmetic settings.
+    pub fn new() -> Interpreter {
+        Interpreter::with_runtime(Runtime::new())
+    }
+
+    pub fn with_runtime(runtime: Runtime) -> Interpreter {
+        Interpreter { runtime }
+    }
+
+    /// Parses and analyzes a program, ready for its functions to be called.
+    /// A `#!` first line is skipped, so scripts can be made executable.
+    /// Errors are also reported to the runtime's diagnostics sink.
+    pub fn load_source(self, source: &str) -> Result<Machine, Error> {
+        let mut source = source.replace('\r', "");
+        if source.starts_with("#!") {
+            // the newline stays, so line numbers in errors still match the file
+            let end = source.find('\n').unwrap_or(source.len());
+            source.replace_range(..end, "");
+        }
+        let parsed = Lexer::new(&source)
+            .and_then(|lexer| DescentParser::new(lexer).analyze())
+            .map_err(Error::Parse);
+        match parsed {
+            Ok(program) => { Machine::new(program, self.runtime) }
+            Err(error) => {
+                self.runtime.report(&error);
+                Err(error)
+            }
+        }
+    }
+
+}
+
+impl Default for Interpreter {
+    fn default() -> Interpreter {
+        Interpreter::new()
+    }
+}
@@ -1,3 +1,5 @@
+use crate::error::ParseError;
+use crate::interner::Symbol;
 use crate::token::Token;
 
 pub struct Lexer {
@@ -6,25 +8,44 @@ pub struct Lexer {
     current_state: i32,
     current_token: Token,
     buffer_string: String,
+    line: usize,
+    // identifiers are interned as they are read; off when only literals are wanted
+    identifiers: bool,
 }
 
 
 impl Lexer {
 
     // called from parser_descent.rs
-    pub fn new(string_input: &str) -> Lexer {
+    pub fn new(string_input: &str) -> Result<Lexer, ParseError> {
+        Self::starting_at(string_input, 1)
+    }
+
+    // lexes a piece of a larger source, counting lines from `line`
+    pub fn starting_at(string_input: &str, line: usize) -> Result<Lexer, ParseError> {
+        Self::build(string_input, line, true)
+    }
+
+    // lexes text that must hold only literals, so nothing gets interned
+    pub fn literals(string_input: &str) -> Result<Lexer, ParseError> {
+        Self::build(string_input, 1, false)
+    }
+
+    fn build(string_input: &str, line: usize, identifiers: bool) -> Result<Lexer, ParseError> {
 
         let mut lexicon = Lexer {
             input_string: string_input.to_string(),
             input_position: 0,
             current_state: 0,
             current_token: Token::SEMICOLON,
-            buffer_string: "".to_string()
+            buffer_string: "".to_string(),
+            line,
+            identifiers,
         };
 
-        lexicon.advance();
+        lexicon.advance()?;
 
-        return lexicon;
+        return Ok(lexicon);
     }
 
     // literally just a call
@@ -32,10 +53,15 @@ impl Lexer {
         self.current_token.clone()
     }
 
+    // source line of the current token
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
     // this version of advance moves through an array of tokens sets current token
     // my version goes through a string and sets current token to the found token
 
-    pub fn advance(&mut self){
+    pub fn advance(&mut self) -> Result<(), ParseError> {
 
         self.current_state = 0;
 
@@ -71,7 +97,7 @@ impl Lexer {
                     "*" => self.current_token = Token::OP_MUL,
                     "/" => self.current_token = Token::OP_DIV,
                     " " => self.current_state = 0,
-                    "\n" => self.current_state = 0,
+                    "\n" => { self.current_state = 0; self.line += 1; },
                     "\t" => self.current_state = 0,
                     _ => self.current_state = 2
                 }
@@ -89,7 +115,7 @@ impl Lexer {
             else if self.current_state == 2 {
 
                 if self.buffer_string.starts_with("<") {
-                    if self.input_string.as_bytes()[self.input_position] as char == '=' {
+                    if self.next_is('=') {
                         self.current_token = Token::OP_NGT;
                         self.input_position = self.input_position + 1;
                     }
@@ -98,7 +124,7 @@ impl Lexer {
                     }
                 }
                 else if self.buffer_string.starts_with(">") {
-                    if self.input_string.as_bytes()[self.input_position] as char == '=' {
+                    if self.next_is('=') {
                         self.current_token = Token::OP_NLT;
                         self.input_position = self.input_position + 1;
                     }
@@ -107,13 +133,16 @@ impl Lexer {
                     }
                 }
                 else if self.buffer_string.starts_with("!") {
-                    if self.input_string.as_bytes()[self.input_position] as char == '=' {
+                    if self.next_is('=') {
                         self.current_token = Token::OP_NEQ;
                         self.input_position = self.input_position + 1;
                     }
+                    else {
+                        return Err(ParseError::Unexpected { expected: "'!='", found: "'!'".to_string(), line: self.line });
+                    }
                 }
                 else if self.buffer_string.starts_with("=") {
-                    if self.input_string.as_bytes()[self.input_position] as char == '=' {
+                    if self.next_is('=') {
                         self.current_token = Token::OP_EQ;
                         self.input_position = self.input_position + 1;
                     }
@@ -122,7 +151,7 @@ impl Lexer {
                     }
                 }
                 else if self.buffer_string.starts_with("-") {
-                    if self.input_string.as_bytes()[self.input_position] as char == '>' {
+                    if self.next_is('>') {
                         self.current_token = Token::ARROW_R;
                         self.input_position = self.input_position + 1;
                     }
@@ -147,7 +176,7 @@ impl Lexer {
                     more = false;
                 }
 
-                if (self.input_string.as_bytes()[0] as char) == '\'' {
+                if (self.buffer_string.as_bytes()[0] as char) == '\'' {
                     while more {
                         self.buffer_string.push(self.input_string.as_bytes()[self.input_position] as char);
                         self.input_position = self.input_position + 1;
@@ -155,11 +184,13 @@ impl Lexer {
                             more = false;
                         }
                     }
-                    self.input_string = self.input_string.replace("\'", "");
-                    self.current_token = Token::LIT_CHAR(self.input_string.parse::<char>().unwrap());
+                    if self.buffer_string.len() < 2 || !self.buffer_string.ends_with('\'') {
+                        return Err(ParseError::Unterminated { what: "character literal", line: self.line });
+                    }
+                    self.current_token = Token::LIT_CHAR(self.character()?);
                 }
 
-                if (self.buffer_string.as_bytes()[0] as char) == '\"' {
+                else if (self.buffer_string.as_bytes()[0] as char) == '\"' {
                     while more {
                         self.buffer_string.push(self.input_string.as_bytes()[self.input_position] as char);
                         self.input_position = self.input_position + 1;
@@ -167,6 +198,9 @@ impl Lexer {
                             more = false;
                         }
                     }
+                    if self.buffer_string.len() < 2 || !self.buffer_string.ends_with('\"') {
+                        return Err(ParseError::Unterminated { what: "string", line: self.line });
+                    }
                     self.buffer_string = self.buffer_string.replace("\"", "");
                     self.current_token = Token::LIT_STRING(self.buffer_string.clone());
                 }
@@ -194,8 +228,7 @@ impl Lexer {
 
 
                     if ((self.buffer_string.as_bytes()[0] as char) == '\'') & ((self.buffer_string.as_bytes()[self.buffer_string.len() - 1] as char) == '\'') {
-                        self.buffer_string = self.buffer_string.replace("\'", "");
-                        self.current_token = Token::LIT_CHAR(self.buffer_string.parse::<char>().unwrap());
+                        self.current_token = Token::LIT_CHAR(self.character()?);
                     }
                     else if ((self.buffer_string.as_bytes()[0] as char) == '\"') & ((self.buffer_string.as_bytes()[self.buffer_string.len() - 1] as char) == '\"'){
                         self.buffer_string = self.buffer_string.replace("\"", "");
@@ -224,11 +257,21 @@ impl Lexer {
                     }
                 }
 
-                if integer {
-                    self.current_token = Token::LIT_I32(self.buffer_string.parse::<i32>().unwrap());
+                if let Some(token) = Self::suffixed(&self.buffer_string, self.line)? {
+                    self.current_token = token;
+                }
+                else if integer {
+                    // literals too large for an i32 are kept as digits for a BigInt
+                    self.current_token = match self.buffer_string.parse::<i32>() {
+                        Ok(number) => Token::LIT_I32(number),
+                        Err(_) => Token::LIT_BIGINT(self.buffer_string.clone()),
+                    };
                 }
                 else if floating {
-                    self.current_token = Token::LIT_F32(self.buffer_string.parse::<f32>().unwrap());
+                    self.current_token = match self.buffer_string.parse::<f32>() {
+                        Ok(number) => Token::LIT_F32(number),
+                        Err(_) => { return Err(ParseError::InvalidLiteral { literal: self.buffer_string.clone(), line: self.line }) }
+                    };
                 }
                 else {
                     self.current_state = 5;
@@ -245,6 +288,7 @@ impl Lexer {
                     "not" => self.current_token = Token::OP_NOT,
                     "and" => self.current_token = Token::OP_AND,
                     "or" => self.current_token = Token::OP_OR,
+                    "as" => self.current_token = Token::OP_AS,
                     "func" => self.current_token = Token::KW_FUNC,
                     "let" => self.current_token = Token::LET,
                     "if" => self.current_token = Token::IF,
@@ -252,21 +296,70 @@ impl Lexer {
                     "while" => self.current_token = Token::WHILE,
                     "print" => self.current_token = Token::PRINT,
                     "return" => self.current_token = Token::RETURN,
+                    "break" => self.current_token = Token::BREAK,
+                    "continue" => self.current_token = Token::CONTINUE,
                     "i32" => self.current_token = Token::TYPE_I32,
                     "int32" => self.current_token = Token::TYPE_I32,
+                    "i64" => self.current_token = Token::TYPE_I64,
+                    "int64" => self.current_token = Token::TYPE_I64,
+                    "u8" => self.current_token = Token::TYPE_U8,
+                    "u32" => self.current_token = Token::TYPE_U32,
+                    "u64" => self.current_token = Token::TYPE_U64,
                     "f32" => self.current_token = Token::TYPE_F32,
                     "float32" => self.current_token = Token::TYPE_F32,
+                    "f64" => self.current_token = Token::TYPE_F64,
+                    "float64" => self.current_token = Token::TYPE_F64,
                     "char" => self.current_token = Token::TYPE_CHAR,
+                    "bool" => self.current_token = Token::TYPE_BOOL,
+                    "string" => self.current_token = Token::TYPE_STRING,
                     "true" => self.current_token = Token::LIT_BOOL(true),
                     "false" => self.current_token = Token::LIT_BOOL(false),
-                    _ => self.current_token = Token::ID(self.buffer_string.clone())
+                    _ if self.identifiers => self.current_token = Token::ID(Symbol::intern(&self.buffer_string)),
+                    _ => { return Err(ParseError::Unexpected { expected: "a literal", found: format!("'{}'", self.buffer_string), line: self.line }) }
                 }
 
                 self.current_state = -1;
                 self.buffer_string = "".to_string();
             }
         }
+
+        Ok(())
     }
 
+    // whether the next unread character is `c`
+    fn next_is(&self, c: char) -> bool {
+        self.input_string.as_bytes().get(self.input_position) == Some(&(c as u8))
+    }
+
+    // the single character between the quotes in the buffer
+    fn character(&mut self) -> Result<char, ParseError> {
+        let literal = self.buffer_string.clone();
+        self.buffer_string = self.buffer_string.replace("\'", "");
+        self.buffer_string.parse::<char>()
+            .map_err(|_| ParseError::InvalidLiteral { literal, line: self.line })
+    }
 
-}
\ No newline at end of file
+    // number with a type suffix, such as `255u8`, `7i64` or `2.5f64`
+    fn suffixed(text: &str, line: usize) -> Result<Option<Token>, ParseError> {
+        if !text.starts_with(|c: char| c.is_ascii_digit()) {
+            return Ok(None);
+        }
+        let Some(suffix) = ["i32", "i64", "u8", "u32", "u64", "f32", "f64"].into_iter()
+            .find(|suffix| text.ends_with(suffix)) else { return Ok(None) };
+        let number = &text[..text.len() - suffix.len()];
+        if !number.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            return Ok(None);
+        }
+        let out_of_range = || ParseError::LiteralOutOfRange { literal: number.to_string(), kind: suffix, line };
+        let token = match suffix {
+            "i32" => number.parse().map(Token::LIT_I32).map_err(|_| out_of_range())?,
+            "i64" => number.parse().map(Token::LIT_I64).map_err(|_| out_of_range())?,
+            "u8" => number.parse().map(Token::LIT_U8).map_err(|_| out_of_range())?,
+            "u32" => number.parse().map(Token::LIT_U32).map_err(|_| out_of_range())?,
+            "u64" => number.parse().map(Token::LIT_U64).map_err(|_| out_of_range())?,
+            "f32" => number.parse().map(Token::LIT_F32).map_err(|_| out_of_range())?,
+            _ => number.parse().map(Token::LIT_F64).map_err(|_| out_of_range())?,
+        };
+        Ok(Some(token))
+    }
+}
@@ -0,0 +1,31 @@
+#![allow(non_snake_case)]
+
+mod builtins;
+mod tree;
+mod parse_tree;
+mod executor;
+mod machine;
+mod analyzer;
+mod resolver;
+mod symbols;
+mod frame;
+mod value;
+mod evaluator;
+mod parser;
+mod token;
+mod lexer;
+mod runtime;
+mod bigint;
+mod error;
+mod interner;
+mod interpreter;
+mod native;
+
+pub use crate::bigint::BigInt;
+pub use crate::error::{AnalysisError, CallSite, Error, ParseError, RuntimeError};
+pub use crate::interner::Symbol;
+pub use crate::interpreter::Interpreter;
+pub use crate::machine::Machine;
+pub use crate::native::{Native, NativeFn};
+pub use crate::runtime::{Coercion, OverflowMode, Runtime};
+pub use crate::value::{Type, Value};
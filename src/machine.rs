@@ -1,24 +1,58 @@
 use std::rc::Rc;
 use crate::analyzer::Analyzer;
+use crate::error::{Error, RuntimeError};
 use crate::executor::Executor;
+use crate::interner::Symbol;
+use crate::resolver::Resolver;
+use crate::runtime::Runtime;
 use crate::tree::ProgramNode;
+use crate::value::Value;
 
+/// A loaded program, with its globals, ready to have its functions called.
 pub struct Machine {
-    rc_program: Rc<ProgramNode>,
+    executor: Executor,
+    rc_runtime: Rc<Runtime>,
 }
 
 impl Machine {
 
-    pub fn new(rc_program: Rc<ProgramNode>) -> Machine {
-        Machine {
-            rc_program
+    /// Analyzes and resolves the program so that its functions can be called.
+    /// Errors are also reported to the runtime's diagnostics sink.
+    pub fn new(rc_program: Rc<ProgramNode>, runtime: Runtime) -> Result<Machine, Error> {
+        let analyzed = Analyzer::new(rc_program.clone(), runtime.natives()).analyze()
+            .and_then(|_| Resolver::new(rc_program.clone()).resolve());
+        if let Err(error) = analyzed {
+            let error = Error::Analysis(error);
+            runtime.report(&error);
+            return Err(error);
         }
+        let rc_runtime = Rc::new(runtime);
+        let executor = Executor::new(rc_program, rc_runtime.clone());
+        Ok(Machine { executor, rc_runtime })
     }
 
-    pub fn run(&self) {
-        let analyzer = Analyzer::new(self.rc_program.clone());
-        analyzer.analyze();
-        let executor = Executor::new(self.rc_program.clone());
-        executor.execute();
+    /// Runs `main` with the runtime's program arguments (see `Executor::execute`).
+    pub fn run(&self) -> Result<Value, Error> {
+        self.run_entry("main")
     }
-}
\ No newline at end of file
+
+    /// Like `run`, starting from another top-level function.
+    pub fn run_entry(&self, entry: &str) -> Result<Value, Error> {
+        self.finish(self.executor.execute(Symbol::intern(entry)))
+    }
+
+    /// Calls a top-level function. A runtime error is also reported to the diagnostics sink.
+    pub fn call(&self, name: &str, arguments: Vec<Value>) -> Result<Value, Error> {
+        self.finish(self.executor.call(Symbol::intern(name), arguments))
+    }
+
+    fn finish(&self, result: Result<Value, RuntimeError>) -> Result<Value, Error> {
+        // output printed before a failure is still flushed
+        let flushed = self.rc_runtime.flush();
+        let result = result.and_then(|value| flushed.map(|_| value)).map_err(Error::Runtime);
+        if let Err(error) = &result {
+            self.rc_runtime.report(error);
+        }
+        result
+    }
+}
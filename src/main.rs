@@ -1,37 +1,51 @@
 #![allow(non_snake_case)]
 
-use std::{env, fs};
-use std::error::Error;
+use std::io;
 use std::fs::read_to_string;
-use std::ops::Deref;
-use clap::{arg, Parser};
-use std::path::PathBuf;
-use clap_logger::{ClapInitLogger, ClapLoglevelArg};
-
-mod tree;
-mod parse_tree;
-mod executor;
-mod machine;
-mod analyzer;
-mod symbols;
-mod frame;
-mod value;
-mod evaluator;
-mod parser;
-mod token;
-mod lexer;
-use crate::token::Token;
-use crate::lexer::Lexer;
-use crate::parse_tree::ParseTree;
-use crate::parser::DescentParser;
-const INDENT : usize = 2;
+use clap::Parser;
+use std::path::{Path, PathBuf};
+use std::io::{Read, Write};
+use log::{debug, warn, LevelFilter};
+
+use PL_Final::{Coercion, Error, Interpreter, OverflowMode, Runtime, Value};
+
+/// Exit code for a program file that cannot be read.
+const EXIT_NO_INPUT: i32 = 66;
+/// Exit code for a program that fails to parse or analyze.
+const EXIT_INVALID_PROGRAM: i32 = 65;
+/// Exit code for a program that fails while running.
+const EXIT_RUNTIME_ERROR: i32 = 70;
+/// Exit code for an integer result from main that is not a valid exit status.
+const EXIT_OUT_OF_RANGE: i32 = 71;
+/// Exit code for command line options that cannot be set up, like an unusable `--allow-fs` directory.
+const EXIT_USAGE: i32 = 64;
+
+const EXIT_STATUS_HELP: &str = "\
+Exit status:
+  0..=255  the integer main returned (0 for any other result); a program may
+           return the codes below itself, so they are only reserved by convention
+  2        the command line could not be parsed
+  64       the --allow-fs directory cannot be used
+  65       the program failed to parse or analyze
+  66       the program file could not be read
+  70       the program failed with a runtime error
+  71       main returned an integer outside 0..=255";
 
 /// Program CLI
 #[derive(Debug, Parser)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, after_help = EXIT_STATUS_HELP)]
 struct Cli {
-    /// Input File
-    file: PathBuf,
+    /// Input file, or - to read the program from stdin
+    #[clap(required_unless_present = "command")]
+    file: Option<PathBuf>,
+
+    /// Run this source text instead of a file; every positional argument goes to the program
+    #[clap(short = 'c', value_name = "SOURCE")]
+    command: Option<String>,
+
+    /// Arguments for the program's main function
+    #[clap(trailing_var_arg = true, allow_hyphen_values = true)]
+    arguments: Vec<String>,
 
     /// Show parser output
     #[clap(short = 'p', long = "parse", group = "action")]
@@ -41,55 +55,168 @@ struct Cli {
     #[clap(short = 'e', long = "execute", group = "action")]
     execute: bool,
 
-    /// Show run output
+    /// Run the program; this is the default action
     #[clap(short = 'r', long = "run", group = "action")]
     run: bool,
-}
 
-enum Logger {
-    info(String),
-    debug,
-    warn,
-    none
+    /// Call this function with the arguments as literals and print its result
+    #[clap(long = "call", value_name = "FUNC", group = "action")]
+    call: Option<String>,
+
+    /// Start the run from this function instead of main
+    #[clap(long = "entry", value_name = "FUNC", default_value = "main", conflicts_with = "call")]
+    entry: String,
+
+    /// What integer arithmetic does on overflow
+    #[clap(long = "overflow", value_enum, default_value_t = OverflowMode::Promote)]
+    overflow: OverflowMode,
+
+    /// Whether strings and numbers mix in arithmetic and comparisons
+    #[clap(long = "coercion", value_enum, default_value_t = Coercion::Strict)]
+    coercion: Coercion,
 
+    /// Let the file builtins use this directory and everything below it
+    #[clap(long = "allow-fs", value_name = "DIR")]
+    allow_fs: Option<PathBuf>,
+
+    /// Seed for the random builtins, to repeat a run exactly
+    #[clap(long = "seed")]
+    seed: Option<u64>,
+
+    /// Log interpreter phases to stderr; repeat for statement and parse tree traces
+    #[clap(short = 'v', long = "verbose", action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+
+    /// Log nothing, not even warnings
+    #[clap(short = 'q', long = "quiet")]
+    quiet: bool,
 }
 
-fn main() {
-    let args = Cli::parse();
-    let cpy = args.file.clone();
+impl Cli {
+
+    // with -c there is no file, so the first positional is a program argument
+    fn program_arguments(&self) -> Vec<String> {
+        match (&self.command, &self.file) {
+            (Some(_), Some(first)) => {
+                let first = first.to_string_lossy().into_owned();
+                std::iter::once(first).chain(self.arguments.iter().cloned()).collect()
+            }
+            _ => { self.arguments.clone() }
+        }
+    }
 
-    let mut input = read_to_string(args.file).expect("Failed to read input file.");
-    while(input.contains('\n')) {
-        let index = input.find('\n');
-        input.remove(index.unwrap());
+    fn source(&self) -> io::Result<String> {
+        if let Some(command) = &self.command {
+            return Ok(command.clone());
+        }
+        let file = self.file.as_deref().unwrap_or(Path::new("-"));
+        if file == Path::new("-") {
+            let mut source = String::new();
+            io::stdin().read_to_string(&mut source)?;
+            Ok(source)
+        } else {
+            read_to_string(file)
+        }
     }
-    while(input.contains('\r')) {
-        let index = input.find('\r');
-        input.remove(index.unwrap());
+
+    fn log_level(&self) -> LevelFilter {
+        if self.quiet {
+            return LevelFilter::Off;
+        }
+        match self.verbose {
+            0 => LevelFilter::Warn,
+            1 => LevelFilter::Info,
+            2 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        }
     }
+}
+
+// diagnostics go to stderr as "[level] message", leaving stdout to the program
+fn init_logger(level: LevelFilter) {
+    env_logger::Builder::new()
+        .filter_level(level)
+        .format(|buf, record| {
+            writeln!(buf, "[{}] {}", record.level().as_str().to_lowercase(), record.args())
+        })
+        .init();
+}
 
-    println!("{:?}", input);
+fn main() {
+    let args = Cli::parse();
+    init_logger(args.log_level());
+
+    let input = match args.source() {
+        Ok(input) => { input }
+        Err(error) => {
+            let file = args.file.as_deref().unwrap_or(Path::new("-"));
+            eprintln!("Cannot read {}: {error}", file.display());
+            std::process::exit(EXIT_NO_INPUT);
+        }
+    };
+    debug!("{:?}", input);
 
     if args.parse {
-        println!("[ALERT] WIP");
+        warn!("WIP");
         //println!("Parsing file: {:?}", args.file);
     }
 
     if args.execute {
-        println!("[ALERT] WIP");
+        warn!("WIP");
         //println!("executing: {:?}", args.file);
     }
 
-    if args.run {
-
-        let mut file_path = PathBuf::new();
-        file_path.push(cpy.deref());
-        //println!("Reading file from path: {:?}", file_path);
+    if args.run || !(args.parse || args.execute) {
+        let mut runtime = Runtime::new();
+        runtime.overflow = args.overflow;
+        runtime.coercion = args.coercion;
+        runtime.set_arguments(args.program_arguments());
+        if let Some(seed) = args.seed {
+            runtime.set_seed(seed);
+        }
+        if let Some(dir) = &args.allow_fs {
+            if let Err(error) = runtime.allow_files(dir) {
+                eprintln!("Cannot allow file access to {}: {error}", dir.display());
+                std::process::exit(EXIT_USAGE);
+            }
+        }
+        // errors have already been reported to stderr by the interpreter
+        let machine = Interpreter::with_runtime(runtime).load_source(&input);
+        let result = match &args.call {
+            Some(function) => {
+                let arguments = args.program_arguments().iter().map(|argument| literal(argument)).collect();
+                let result = machine.and_then(|machine| machine.call(function, arguments));
+                match result {
+                    Ok(value) => {
+                        println!("{value}");
+                        Ok(Value::Nil)
+                    }
+                    Err(error) => { Err(error) }
+                }
+            }
+            None => { machine.and_then(|machine| machine.run_entry(&args.entry)) }
+        };
+        std::process::exit(exit_code(&result));
+    }
+}
 
-        // create recursive descent parser
-        let lexer = Lexer::new(&input);
-        let mut parser = DescentParser::new(lexer);
+// a --call argument is read as the literal it would be in a program, so `5u8`, `'a'`
+// and `"two words"` keep their types; anything else (`nan`, `hello`) is a string
+fn literal(text: &str) -> Value {
+    Value::parse_literal(text).unwrap_or_else(|| Value::Str(text.to_string()))
+}
 
-        parser.analyze();
+// an integer returned from main is the exit status; one the shell would truncate to
+// its low 8 bits (so 256 would read as success) becomes a plain failure instead
+fn exit_code(result: &Result<Value, Error>) -> i32 {
+    match result {
+        Ok(value) => {
+            match value.to_i128() {
+                Some(code) => { u8::try_from(code).map_or(EXIT_OUT_OF_RANGE, i32::from) }
+                None => { 0 }
+            }
+        }
+        Err(Error::Runtime(_)) => { EXIT_RUNTIME_ERROR }
+        Err(Error::Parse(_) | Error::Analysis(_)) => { EXIT_INVALID_PROGRAM }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,43 @@
+use std::fmt;
+use crate::error::RuntimeError;
+use crate::interner::Symbol;
+use crate::runtime::Runtime;
+use crate::value::Value;
+
+/// Body of a native function. It gets the runtime of the calling program and
+/// exactly as many arguments as the function declares.
+pub type NativeFn = dyn Fn(&Runtime, Vec<Value>) -> Result<Value, RuntimeError>;
+
+/// A function implemented in Rust that programs call like one of their own.
+pub struct Native {
+    pub name: Symbol,
+    pub arity: usize,
+    function: Box<NativeFn>,
+}
+
+impl Native {
+
+    pub fn new<F>(name: &str, arity: usize, function: F) -> Native
+    where
+        F: Fn(&Runtime, Vec<Value>) -> Result<Value, RuntimeError> + 'static,
+    {
+        Native { name: Symbol::intern(name), arity, function: Box::new(function) }
+    }
+
+    pub fn call(&self, runtime: &Runtime, arguments: Vec<Value>) -> Result<Value, RuntimeError> {
+        if arguments.len() != self.arity {
+            return Err(RuntimeError::ArityMismatch {
+                function: self.name,
+                expected: self.arity,
+                found: arguments.len(),
+            });
+        }
+        (self.function)(runtime, arguments)
+    }
+}
+
+impl fmt::Debug for Native {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<native {}/{}>", self.name, self.arity)
+    }
+}
@@ -3,18 +3,22 @@
 use std::alloc::GlobalAlloc;
 use std::rc::{Rc, self};
 use clap::builder::NonEmptyStringValueParser;
+use log::trace;
 //use rand::distributions::Exp;
 
+use crate::error::ParseError;
 use crate::token::{Token, self};
 use crate::tree::{AssignNode, BlockNode, ExprNode, FuncNode, IfNode, WhileNode, LetNode, Parameter, PrintNode, ProgramNode, ReturnNode, StmtNode};
-use crate::value::Value;
+use crate::interner::Symbol;
+use crate::value::{Type, Value};
 use crate::evaluator::Evaluator;
 
 
 #[derive(Clone)]
 pub struct ParseTree {
     pub token : Token,
-    pub children : Vec<Box<ParseTree>>
+    pub children : Vec<Box<ParseTree>>,
+    pub line : usize,
 }
 
 impl ParseTree {
@@ -22,7 +26,16 @@ impl ParseTree {
     pub fn new(token : Token) -> ParseTree {
         ParseTree {
             token,
-            children : vec![]
+            children : vec![],
+            line : 0,
+        }
+    }
+
+    // tags this node and all its children with a source line
+    pub fn set_line(&mut self, line : usize) {
+        self.line = line;
+        for child in &mut self.children {
+            child.set_line(line);
         }
     }
 
@@ -34,10 +47,10 @@ impl ParseTree {
         format!("{:?}", self.token)
     }
 
+    // one trace line per node, indented by depth
     fn print_recursively(&self, level : usize) {
         let shift = 2*level;
-        print!("{:1$}", "", shift);
-        println!("{}", self.node_string());
+        trace!("{:1$}{2}", "", shift, self.node_string());
         for child in &self.children {
             child.as_ref().print_recursively(level+1);
         }
@@ -47,32 +60,50 @@ impl ParseTree {
         self.print_recursively(0);
     }
 
-    pub fn funcNode_grow(&self) -> FuncNode{
+    // the already interned name of an identifier node
+    fn symbol(&self) -> Result<Symbol, ParseError> {
+        match self.token {
+            Token::ID(symbol) => { Ok(symbol) }
+            _ => { Err(self.unexpected("an identifier")) }
+        }
+    }
+
+    // error for a node that cannot appear where it was found
+    fn unexpected(&self, expected: &'static str) -> ParseError {
+        ParseError::Unexpected { expected, found: self.token.described(), line: self.line }
+    }
+
+    pub fn funcNode_grow(&self) -> Result<FuncNode, ParseError> {
         let mut parameters = vec![];
         for n in 1..self.children[0].children.len() - 1{
-            parameters.push(Parameter::new(self.children[0].children[n].token.string().parse().unwrap()));
+            let parameter = &self.children[0].children[n];
+            // ID, or ID COLON type
+            let kind = parameter.children.get(2).and_then(|tree| Type::from_token(&tree.token));
+            parameters.push(Parameter::new(parameter.symbol()?, kind));
         }
 
         let mut block = BlockNode::new();
         let mut stmt: StmtNode;
 
-        for n in 0..self.children[1].children.len() - 1 {
-            stmt = self.children[1].children[n].stmtNode_grow();
+        // the body comes last, after an optional `-> type`
+        let body = &self.children[self.children.len() - 1];
+        for n in 0..body.children.len() - 1 {
+            stmt = body.children[n].stmtNode_grow()?;
             block.statements.push(Rc::new(stmt));
         }
 
         let func = FuncNode::new(
-            self.children[0].token.string().to_string(),
+            self.children[0].symbol()?,
             parameters,
             block);
         
-        return func;
+        return Ok(func);
     }
 
-    pub fn stmtNode_grow(&self) -> StmtNode {
+    pub fn stmtNode_grow(&self) -> Result<StmtNode, ParseError> {
         match self.token {
             Token::RETURN => {
-                return StmtNode::Return(ReturnNode::new(self.children[0].exprNode_grow()));
+                return Ok(StmtNode::Return(ReturnNode::new(self.children[0].exprNode_grow()?)));
             },
             Token::IF => {
                 let mut trueBlock = BlockNode::new();
@@ -84,17 +115,27 @@ impl ParseTree {
                         break;
                     } 
                     else {
-                        stmt = self.children[n].stmtNode_grow();
+                        stmt = self.children[n].stmtNode_grow()?;
                         trueBlock.statements.push(Rc::new(stmt));
                     }
                 }
-                
-                for mut n in 1..self.children[self.children.len() - 1].children.len() - 1 {
-                    stmt = self.children[self.children.len() - 1].children[n].stmtNode_grow();
-                    falseBlock.statements.push(Rc::new(stmt));
+
+                // the else branch is optional; `else if` holds a single nested if statement
+                let last = &self.children[self.children.len() - 1];
+                if last.token == Token::ELSE {
+                    if last.children[0].token == Token::IF {
+                        stmt = last.children[0].stmtNode_grow()?;
+                        falseBlock.statements.push(Rc::new(stmt));
+                    }
+                    else {
+                        for n in 1..last.children.len() - 1 {
+                            stmt = last.children[n].stmtNode_grow()?;
+                            falseBlock.statements.push(Rc::new(stmt));
+                        }
+                    }
                 }
                 
-                return StmtNode::If(IfNode::new(self.children[0].exprNode_grow(), trueBlock, falseBlock));
+                return Ok(StmtNode::If(IfNode::new(self.children[0].exprNode_grow()?, trueBlock, falseBlock)));
             },
             Token::WHILE => {
                 let mut trueBlock = BlockNode::new();
@@ -106,7 +147,7 @@ impl ParseTree {
                         break;
                     } 
                     else {
-                        stmt = self.children[n].stmtNode_grow();
+                        stmt = self.children[n].stmtNode_grow()?;
                         trueBlock.statements.push(Rc::new(stmt));
                     }
                 }
@@ -114,97 +155,103 @@ impl ParseTree {
                 if(self.children.len() > 0){
                     if(self.children[self.children.len() - 1].children.len() > 0){
                         for mut n in 1..self.children[self.children.len() - 1].children.len() - 1 {
-                            stmt = self.children[self.children.len() - 1].children[n].stmtNode_grow();
+                            stmt = self.children[self.children.len() - 1].children[n].stmtNode_grow()?;
                             falseBlock.statements.push(Rc::new(stmt));
                         }
                     }
                 }
                 
-                return StmtNode::While(WhileNode::new(self.children[0].exprNode_grow(), trueBlock));},
+                return Ok(StmtNode::While(WhileNode::new(self.children[0].exprNode_grow()?, trueBlock)));},
             Token::PRINT => {
-                return StmtNode::Print(PrintNode::new(self.children[0].exprNode_grow()));
+                // only a literal written in the source is a template, never a string computed at runtime
+                let template = self.children.len() > 1
+                    && matches!(&self.children[0].token, Token::LIT_STRING(text) if text.contains("{}"));
+                let exprs = self.children.iter().map(|child| child.exprNode_grow()).collect::<Result<_, _>>()?;
+                return Ok(StmtNode::Print(PrintNode::new(exprs, template)));
             },
             Token::OP_ASSIGN => {
-                return StmtNode::Assign(AssignNode::new(self.children[0].token.string().parse::<String>().unwrap(), self.children[1].exprNode_grow()));
+                return Ok(StmtNode::Assign(AssignNode::new(self.children[0].symbol()?, self.children[1].exprNode_grow()?)));
             },
+            Token::BREAK => Ok(StmtNode::Break),
+            Token::CONTINUE => Ok(StmtNode::Continue),
             Token::LET => {
-                return StmtNode::Let(LetNode::new(self.children[0].token.string().to_string(),Value::Nil));
+                let declaration = &self.children[0];
+                if declaration.token == Token::COLON {
+                    let kind = Type::from_token(&declaration.children[1].token)
+                        .ok_or_else(|| declaration.children[1].unexpected("a type"))?;
+                    return Ok(StmtNode::Let(LetNode::new(declaration.children[0].symbol()?, Value::Nil, Some(kind))));
+                }
+                return Ok(StmtNode::Let(LetNode::new(declaration.symbol()?, Value::Nil, None)));
             },
-            _ => {panic!()}
+            _ => { Err(self.unexpected("a statement")) }
         }
     }
 
-    pub fn exprNode_grow(&self) -> ExprNode {
+    pub fn exprNode_grow(&self) -> Result<ExprNode, ParseError> {
         match self.token {
             Token::PAREN_L => {
                 return self.children[0].exprNode_grow();
             },
-            Token::PAREN_R => {panic!()},
             Token::OP_ADD => {
-                return ExprNode::Add(Rc::from(self.children[0].exprNode_grow()), Rc::from(self.children[1].exprNode_grow()));
+                return Ok(ExprNode::Add(Rc::from(self.children[0].exprNode_grow()?), Rc::from(self.children[1].exprNode_grow()?)));
             },
             Token::OP_SUB => {
                 if self.children.len() > 1 {
-                    return ExprNode::Sub(Rc::from(self.children[0].exprNode_grow()), Rc::from(self.children[1].exprNode_grow()));
+                    return Ok(ExprNode::Sub(Rc::from(self.children[0].exprNode_grow()?), Rc::from(self.children[1].exprNode_grow()?)));
                 }
                 else {
-                    return ExprNode::Sub(Rc::from(ExprNode::Val(Value::I32(0))), Rc::from(self.children[0].exprNode_grow()));
+                    return Ok(ExprNode::Sub(Rc::from(ExprNode::Val(Value::I32(0))), Rc::from(self.children[0].exprNode_grow()?)));
                 }
             }
             Token::OP_MUL => {
-                return ExprNode::Mul(Rc::from(self.children[0].exprNode_grow()), Rc::from(self.children[1].exprNode_grow()));
+                return Ok(ExprNode::Mul(Rc::from(self.children[0].exprNode_grow()?), Rc::from(self.children[1].exprNode_grow()?)));
             },
             Token::OP_DIV => {
-                return ExprNode::Div(Rc::from(self.children[0].exprNode_grow()), Rc::from(self.children[1].exprNode_grow()));
+                return Ok(ExprNode::Div(Rc::from(self.children[0].exprNode_grow()?), Rc::from(self.children[1].exprNode_grow()?)));
             },
 
             Token::OP_EQ => {
-                return ExprNode::Equal(Rc::from(self.children[0].exprNode_grow()), Rc::from(self.children[1].exprNode_grow()));},
+                return Ok(ExprNode::Equal(Rc::from(self.children[0].exprNode_grow()?), Rc::from(self.children[1].exprNode_grow()?)));},
             Token::OP_LT => {
-                return ExprNode::LessThan(Rc::from(self.children[0].exprNode_grow()), Rc::from(self.children[1].exprNode_grow()));},
+                return Ok(ExprNode::LessThan(Rc::from(self.children[0].exprNode_grow()?), Rc::from(self.children[1].exprNode_grow()?)));},
             Token::OP_GT => {
-                return ExprNode::GreaterThan(Rc::from(self.children[0].exprNode_grow()), Rc::from(self.children[1].exprNode_grow()));},
+                return Ok(ExprNode::GreaterThan(Rc::from(self.children[0].exprNode_grow()?), Rc::from(self.children[1].exprNode_grow()?)));},
             Token::OP_NEQ => {
-                return ExprNode::NotEqual(Rc::from(self.children[0].exprNode_grow()), Rc::from(self.children[1].exprNode_grow()));},
+                return Ok(ExprNode::NotEqual(Rc::from(self.children[0].exprNode_grow()?), Rc::from(self.children[1].exprNode_grow()?)));},
             Token::OP_NLT => {
-                return ExprNode::GreaterThanEqual(Rc::from(self.children[0].exprNode_grow()), Rc::from(self.children[1].exprNode_grow()));},
+                return Ok(ExprNode::GreaterThanEqual(Rc::from(self.children[0].exprNode_grow()?), Rc::from(self.children[1].exprNode_grow()?)));},
             Token::OP_NGT => {
-                return ExprNode::LessThanEqual(Rc::from(self.children[0].exprNode_grow()), Rc::from(self.children[1].exprNode_grow()));},
+                return Ok(ExprNode::LessThanEqual(Rc::from(self.children[0].exprNode_grow()?), Rc::from(self.children[1].exprNode_grow()?)));},
 
             Token::OP_NOT => {
-                return ExprNode::Not(Rc::from(self.children[0].exprNode_grow()));},
+                return Ok(ExprNode::Not(Rc::from(self.children[0].exprNode_grow()?)));},
             Token::OP_AND => {
-                return ExprNode::And(Rc::from(self.children[0].exprNode_grow()), Rc::from(self.children[1].exprNode_grow()));},
+                return Ok(ExprNode::And(Rc::from(self.children[0].exprNode_grow()?), Rc::from(self.children[1].exprNode_grow()?)));},
             Token::OP_OR => {
-                return ExprNode::Or(Rc::from(self.children[0].exprNode_grow()), Rc::from(self.children[1].exprNode_grow()));},
+                return Ok(ExprNode::Or(Rc::from(self.children[0].exprNode_grow()?), Rc::from(self.children[1].exprNode_grow()?)));},
 
-            Token::ID(_) => {
+            Token::OP_AS => {
+                let kind = Type::from_token(&self.children[1].token)
+                    .ok_or_else(|| self.children[1].unexpected("a type"))?;
+                return Ok(ExprNode::Cast(Rc::from(self.children[0].exprNode_grow()?), kind));},
+
+            Token::ID(symbol) => {
                 if(self.children.len() > 0){
                     let mut exprs = vec![];
                     for n in 0..self.children[0].children.len(){
-                        exprs.insert(n, Rc::new(self.children[0].children[n].exprNode_grow()));
+                        exprs.insert(n, Rc::new(self.children[0].children[n].exprNode_grow()?));
                     }
-                    return ExprNode::Call(self.token.string().to_string(), exprs)
+                    return Ok(ExprNode::call(symbol, exprs, self.line))
                 }
-                return ExprNode::Var(self.token.string().to_string());
+                return Ok(ExprNode::var(symbol));
             },
 
-            Token::LIT_I32(_) => {
-                return ExprNode::Val(Value::I32(self.token.string().parse::<i32>().unwrap()));
-            },
-            Token::LIT_F32(_) => {
-                return ExprNode::Val(Value::F32(self.token.string().parse::<f32>().unwrap()));
-            },
-            Token::LIT_CHAR(_) => {
-                return ExprNode::Val(Value::Chars(String::from(self.token.string().parse::<char>().unwrap())));
-            },
-            Token::LIT_STRING(_) => {
-                return ExprNode::Val(Value::Chars((self.token.string() as &str).parse().unwrap()));
-            },
-            Token::LIT_BOOL(_) => {
-                return ExprNode::Val(Value::Bool(self.token.string().parse::<bool>().unwrap()));
+            Token::LIT_I32(_) | Token::LIT_I64(_) | Token::LIT_U8(_) | Token::LIT_U32(_) | Token::LIT_U64(_)
+            | Token::LIT_BIGINT(_) | Token::LIT_F32(_) | Token::LIT_F64(_)
+            | Token::LIT_CHAR(_) | Token::LIT_STRING(_) | Token::LIT_BOOL(_) => {
+                return Value::from_token(&self.token).map(ExprNode::Val).ok_or_else(|| self.unexpected("a literal"));
             },
-            _ => {panic!()}
+            _ => { Err(self.unexpected("an expression")) }
         }
     }
 }
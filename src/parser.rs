@@ -4,14 +4,14 @@ Implement the main parser using recursive descent. Use a Pratt parser for expres
 parsing. Optionally, add error localization and descriptions.
  */
 
-use crate::machine::{self, Machine};
+use crate::error::ParseError;
 use crate::token::{Token, self};
 use crate::lexer::Lexer;
 use crate::parse_tree::ParseTree;
 use crate::tree::{AssignNode, BlockNode, ExprNode, FuncNode, IfNode, LetNode, Parameter, PrintNode, ProgramNode, ReturnNode, StmtNode};
 use std::{env, string};
 use std::rc::Rc;
-use std::fs;
+use log::{debug, trace};
 use std::fs::File;
 use std::io::Read;
 //use std::fs::File;
@@ -40,19 +40,21 @@ impl Token {
 
             Token::COLON => (2,3),
 
-            Token::OP_ADD => (2,3),
-            Token::OP_SUB => (2,3),
-            Token::OP_MUL => (4,5),
-            Token::OP_DIV => (4,5),
-
-            Token::OP_EQ => (2,1),
-            Token::OP_LT => (2,1),
-            Token::OP_GT => (2,1),
-            Token::OP_NEQ => (2,1),
-            Token::OP_NLT => (2,1),
-            Token::OP_NGT => (2,1),
-
-            Token::OP_NOT => (5,6),
+            // precedence from loosest to tightest: or, and, not, relational, additive, multiplicative, as
+            Token::OP_ADD => (8,9),
+            Token::OP_SUB => (8,9),
+            Token::OP_MUL => (10,11),
+            Token::OP_DIV => (10,11),
+            Token::OP_AS => (12,13),
+
+            Token::OP_EQ => (6,7),
+            Token::OP_LT => (6,7),
+            Token::OP_GT => (6,7),
+            Token::OP_NEQ => (6,7),
+            Token::OP_NLT => (6,7),
+            Token::OP_NGT => (6,7),
+
+            Token::OP_NOT => (5,5),
             Token::OP_AND => (4,5),
             Token::OP_OR => (2,3),
 
@@ -61,21 +63,49 @@ impl Token {
             Token::ID(_) => (1,1),
 
             Token::LIT_I32(_) => (1,1),
+            Token::LIT_I64(_) => (1,1),
+            Token::LIT_U8(_) => (1,1),
+            Token::LIT_U32(_) => (1,1),
+            Token::LIT_U64(_) => (1,1),
+            Token::LIT_BIGINT(_) => (1,1),
             Token::LIT_F32(_) => (1,1),
+            Token::LIT_F64(_) => (1,1),
             Token::LIT_CHAR(_) => (1,1),
             Token::LIT_STRING(_) => (1,1),
             Token::LIT_BOOL(_) => (1,1),
 
-            Token::EOI => (0,0),
-            _ => {
-                panic!("Missing binding powers for token {:?}", token);
-            }
+            // anything else ends the expression
+            _ => (0,0),
         }
     }
 
     fn left_bp(&self) -> i32 { Token::binding_powers(self).0 }
     fn right_bp(&self) -> i32 { Token::binding_powers(self).1 }
 
+    // what `expect` names a missing token in parse errors
+    fn expected(&self) -> &'static str {
+        match self {
+            Token::ID(_) => "an identifier",
+            Token::PAREN_L => "'('",
+            Token::PAREN_R => "')'",
+            Token::BRACKET_L => "'['",
+            Token::BRACKET_R => "']'",
+            Token::COLON => "':'",
+            Token::SEMICOLON => "';'",
+            Token::ARROW_R => "'->'",
+            Token::EOI => "end of input",
+            _ => "another token",
+        }
+    }
+
+    // how a token is named in parse errors
+    pub(crate) fn described(&self) -> String {
+        match self {
+            Token::EOI => { "end of input".to_string() }
+            _ => { format!("'{}'", self.string()) }
+        }
+    }
+
 }
 
 impl PrattParser {
@@ -83,26 +113,30 @@ impl PrattParser {
         PrattParser { lexer }
     }
 
-    pub fn analyze(&mut self) -> ParseTree {
-        self.pratt_driver(Token::EOI.right_bp() )
+    pub fn analyze(&mut self) -> Result<ParseTree, ParseError> {
+        let output = self.pratt_driver(Token::EOI.right_bp())?;
+        // the whole expression has to be used up
+        if !self.peek(Token::EOI) {
+            return Err(self.unexpected("an operator"));
+        }
+        Ok(output)
     }
 
-    fn pratt_driver(&mut self, requested_bp: i32) -> ParseTree {
+    fn pratt_driver(&mut self, requested_bp: i32) -> Result<ParseTree, ParseError> {
         let mut current_token = self.current();
-        self.advance();
+        self.advance()?;
 
-        let mut left_denotation = self.func_prefix(current_token.clone());
+        let mut left_denotation = self.func_prefix(current_token.clone())?;
 
-        if (Token::ID(String::new()) == current_token.clone() ) & ( self.current() == Token:: PAREN_L){
+        if (Token::id() == current_token.clone() ) & ( self.current() == Token:: PAREN_L){
             left_denotation = ParseTree::new(current_token.clone());
             let temp = self.current();
-            left_denotation.push(self.func_call(temp.clone()));
-            self.advance();
+            left_denotation.push(self.func_call(temp.clone())?);
+            self.expect(Token::PAREN_R)?;
         }
 
         if left_denotation.token == Token::PAREN_L {
-            left_denotation.push(ParseTree::new(Token::PAREN_R));
-            self.advance();
+            left_denotation.push(self.expect(Token::PAREN_R)?);
         }
 
         loop {
@@ -110,270 +144,232 @@ impl PrattParser {
             // compare binding powers
             if requested_bp >= current_token.left_bp() {
                 // finish subexpression (requested rbp >= curr lbp)
-                return left_denotation;
+                return Ok(left_denotation);
             }
             // go on with subexpression (requested rbp < curr lbp)
-            self.advance();
-            left_denotation = self.func_infix(current_token, left_denotation);
+            self.advance()?;
+            left_denotation = self.func_infix(current_token, left_denotation)?;
         }
     }
 
-    fn func_call(&mut self, token: Token) -> ParseTree {
+    fn func_call(&mut self, token: Token) -> Result<ParseTree, ParseError> {
         let mut output = ParseTree::new(self.current());
-        self.advance();
+        self.advance()?;
         if self.peek(Token::PAREN_R) {
-            return output;
-        }
-        if self.peek(Token::id()){
-            let mut token_string : String = "".to_string();
-            while (self.current() != Token::COMMA) & (self.current() != Token::PAREN_R){
-                token_string.push_str(&(self.current().string().to_string() + " "));
-                self.advance();
-            }
-            let token_str : &'static str = token_string.leak();
-
-            let prattlexer = Lexer::new(token_str);
-            let mut prattparser = PrattParser::new(prattlexer);
-            output.push(prattparser.analyze());
-        }
-        else if self.peek(Token::lit_i32()){
-            let mut token_string : String = "".to_string();
-            while (self.current() != Token::COMMA) & (self.current() != Token::PAREN_R){
-                token_string.push_str(&(self.current().string().to_string() + " "));
-                self.advance();
-            }
-            let token_str : &'static str = token_string.leak();
-
-            let prattlexer = Lexer::new(token_str);
-            let mut prattparser = PrattParser::new(prattlexer);
-            output.push(prattparser.analyze());
-        }
-        else if self.peek(Token::lit_f32()){
-            let mut token_string : String = "".to_string();
-            while (self.current() != Token::COMMA) & (self.current() != Token::PAREN_R){
-                token_string.push_str(&(self.current().string().to_string() + " "));
-                self.advance();
-            }
-            let token_str : &'static str = token_string.leak();
-
-            let prattlexer = Lexer::new(token_str);
-            let mut prattparser = PrattParser::new(prattlexer);
-            output.push(prattparser.analyze());
-        }
-        while self.accept(Token::COMMA) {
-            if self.peek(Token::id()){
-                let mut token_string : String = "".to_string();
-                while (self.current() != Token::COMMA) & (self.current() != Token::PAREN_R){
-                    token_string.push_str(&(self.current().string().to_string() + " "));
-                    self.advance();
-                }
-                let token_str : &'static str = token_string.leak();
-    
-                let prattlexer = Lexer::new(token_str);
-                let mut prattparser = PrattParser::new(prattlexer);
-                output.push(prattparser.analyze());
-            }
-            else if self.peek(Token::lit_i32()){
-                let mut token_string : String = "".to_string();
-                while (self.current() != Token::COMMA) & (self.current() != Token::PAREN_R){
-                    token_string.push_str(&(self.current().string().to_string() + " "));
-                    self.advance();
-                }
-                let token_str : &'static str = token_string.leak();
-    
-                let prattlexer = Lexer::new(token_str);
-                let mut prattparser = PrattParser::new(prattlexer);
-                output.push(prattparser.analyze());
-            }
-            else if self.peek(Token::lit_f32()){
-                let mut token_string : String = "".to_string();
-                while (self.current() != Token::COMMA) & (self.current() != Token::PAREN_R){
-                    token_string.push_str(&(self.current().string().to_string() + " "));
-                    self.advance();
-                }
-                let token_str : &'static str = token_string.leak();
-    
-                let prattlexer = Lexer::new(token_str);
-                let mut prattparser = PrattParser::new(prattlexer);
-                output.push(prattparser.analyze());
-            }
+            return Ok(output);
+        }
+        output.push(self.func_argument()?);
+        while self.accept(Token::COMMA)? {
+            output.push(self.func_argument()?);
         }
         
-        return output;
+        return Ok(output);
         
     }
 
-    fn func_prefix(&mut self, token: Token) -> ParseTree {
+    // re-lexes the tokens of one call argument and parses them as an expression;
+    // commas and parentheses inside a nested call belong to that call
+    fn func_argument(&mut self) -> Result<ParseTree, ParseError> {
+        let mut token_string : String = "".to_string();
+        let mut depth = 0;
+        while !(((self.current() == Token::COMMA) | (self.current() == Token::PAREN_R)) & (depth == 0)) && !self.peek(Token::EOI) {
+            if self.current() == Token::PAREN_L {
+                depth += 1;
+            }
+            if self.current() == Token::PAREN_R {
+                depth -= 1;
+            }
+            token_string.push_str(&(self.current().string().to_string() + " "));
+            self.advance()?;
+        }
+        let token_str : &'static str = token_string.leak();
+
+        let prattlexer = Lexer::starting_at(token_str, self.lexer.line())?;
+        let mut prattparser = PrattParser::new(prattlexer);
+        prattparser.analyze()
+    }
+
+    fn func_prefix(&mut self, token: Token) -> Result<ParseTree, ParseError> {
         match token {
             Token::ID(_) => {
-                ParseTree::new(token.clone())
+                Ok(ParseTree::new(token.clone()))
             }
             Token::LIT_I32(_) => {
-                ParseTree::new(token.clone())
+                Ok(ParseTree::new(token.clone()))
+            }
+            Token::LIT_I64(_) | Token::LIT_U8(_) | Token::LIT_U32(_) | Token::LIT_U64(_) => {
+                Ok(ParseTree::new(token.clone()))
+            }
+            Token::LIT_BIGINT(_) => {
+                Ok(ParseTree::new(token.clone()))
             }
             Token::LIT_F32(_) => {
-                ParseTree::new(token.clone())
+                Ok(ParseTree::new(token.clone()))
+            }
+            Token::LIT_F64(_) => {
+                Ok(ParseTree::new(token.clone()))
             }
             Token::LIT_CHAR(_) => {
-                ParseTree::new(token.clone())
+                Ok(ParseTree::new(token.clone()))
             }
             Token::LIT_STRING(_) => {
-                ParseTree::new(token.clone())
+                Ok(ParseTree::new(token.clone()))
             }
             Token::LIT_BOOL(_) => {
-                ParseTree::new(token.clone())
+                Ok(ParseTree::new(token.clone()))
             }
             Token::TYPE_I32 => {
-                ParseTree::new(token.clone())
+                Ok(ParseTree::new(token.clone()))
             }
             Token::TYPE_CHAR => {
-                ParseTree::new(token.clone())
+                Ok(ParseTree::new(token.clone()))
             }
             Token::TYPE_F32 => {
-                ParseTree::new(token.clone())
+                Ok(ParseTree::new(token.clone()))
             }
-            Token::PAREN_R => {
-                ParseTree::new(token.clone())
+            Token::TYPE_I64 | Token::TYPE_U8 | Token::TYPE_U32 | Token::TYPE_U64 | Token::TYPE_F64
+            | Token::TYPE_BOOL | Token::TYPE_STRING => {
+                Ok(ParseTree::new(token.clone()))
             }
             Token::PAREN_L => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
             Token::OP_NOT => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(right_denotation);
-                return node;
-            }
-            Token::KW_FUNC => {
-                let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
-                node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
             Token::OP_SUB => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
             //Token::OP_ADD => { todo!() }
             //Token::OP_ASSIGN => { todo!() }
-            Token::EOI => {
-                ParseTree::new(token.clone())
-            }
             _ => {
-                panic!("Missing prefix function for token {:?}", token);
+                Err(ParseError::Unexpected { expected: "an expression", found: token.described(), line: self.lexer.line() })
             }
         }
     }
 
-    fn func_infix(&mut self, token: Token, left_denotation : ParseTree) -> ParseTree {
+    fn func_infix(&mut self, token: Token, left_denotation : ParseTree) -> Result<ParseTree, ParseError> {
         match token {
             //Token::LIT_I32(_) => { todo!() }
             Token::OP_ADD => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(left_denotation);
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
             Token::COLON => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(left_denotation);
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
             Token::OP_AND => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(left_denotation);
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
             Token::OP_OR => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(left_denotation);
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
+            }
+            Token::OP_AS => {
+                let mut node = ParseTree::new(token.clone());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
+                if !right_denotation.token.is_type() {
+                    return Err(ParseError::Unexpected { expected: "a type after 'as'", found: right_denotation.token.described(), line: self.lexer.line() });
+                }
+                node.push(left_denotation);
+                node.push(right_denotation);
+                return Ok(node);
             }
             Token::OP_EQ => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(left_denotation);
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
             Token::OP_LT => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(left_denotation);
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
             Token::OP_GT => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(left_denotation);
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
             Token::OP_NEQ => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(left_denotation);
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
             Token::OP_NLT => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(left_denotation);
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
             Token::OP_NGT => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(left_denotation);
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
             Token::OP_SUB => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(left_denotation);
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
             Token::OP_DIV => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(left_denotation);
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
             Token::OP_MUL => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(left_denotation);
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
             Token::OP_ASSIGN => {
                 let mut node = ParseTree::new(token.clone());
-                let right_denotation = self.pratt_driver(token.right_bp());
+                let right_denotation = self.pratt_driver(token.right_bp())?;
                 node.push(left_denotation);
                 node.push(right_denotation);
-                return node;
+                return Ok(node);
             }
-            Token::EOI => { todo!() }
             _ => {
-                panic!("Missing infix function for token {:?}", token);
+                Err(ParseError::Unexpected { expected: "an operator", found: token.described(), line: self.lexer.line() })
             }
         }
     }
@@ -385,37 +381,41 @@ impl PrattParser { // utility functions for lexer
         self.lexer.current()
     }
 
-    fn advance(&mut self) {
-        self.lexer.advance();
+    fn advance(&mut self) -> Result<(), ParseError> {
+        self.lexer.advance()
     }
 
-    fn expect(&mut self, symbol: Token) -> ParseTree{
+    fn expect(&mut self, symbol: Token) -> Result<ParseTree, ParseError> {
         let output: ParseTree;
         if self.current() == symbol {
             output = ParseTree::new(self.current());
-            self.advance();
+            self.advance()?;
         } else {
 
-            println!("{:?}", self.current());
-            println!("{:?}", symbol);
+            debug!("expected {:?}, found {:?}", symbol, self.current());
 
-            panic!("Did not expect {:?}!", self.current());
+            return Err(self.unexpected(symbol.expected()));
         }
 
-        return output;
+        return Ok(output);
     }
 
-    // similar to expect, but doesnt crash if expected symbol is not found
+    // similar to expect, but doesnt fail if expected symbol is not found
     // returns boolean
-    fn accept(&mut self, symbol: Token) -> bool {
+    fn accept(&mut self, symbol: Token) -> Result<bool, ParseError> {
         if self.current() == symbol {
-            self.advance();
-            true
+            self.advance()?;
+            Ok(true)
         } else {
-            false
+            Ok(false)
         }
     }
 
+    // error for a token that cannot appear here
+    fn unexpected(&mut self, expected: &'static str) -> ParseError {
+        ParseError::Unexpected { expected, found: self.current().described(), line: self.lexer.line() }
+    }
+
     // checks that current symbol is equivalent to a given symbol
     // used for block lists
     fn peek(&mut self, symbol: Token) -> bool {
@@ -431,272 +431,339 @@ pub fn new(lexer: Lexer) -> DescentParser {
     }
 }
 
-    pub fn analyze(&mut self) {
+    pub fn analyze(&mut self) -> Result<Rc<ProgramNode>, ParseError> {
         
         let mut program = ProgramNode::new();
         self.indent = 0;
         while self.peek(Token::KW_FUNC) {
             self.tree = ParseTree::new(Token::KW_FUNC);
-            self.tree = self.parse_func(self.tree.clone());
+            self.tree = self.parse_func(self.tree.clone())?;
             
             self.tree.print();
             // make call to transform function node into executables
             
-            program.func_nodes.push(Rc::new(ParseTree::funcNode_grow(&self.tree)));
+            program.func_nodes.push(Rc::new(ParseTree::funcNode_grow(&self.tree)?));
+        }
+        if !self.peek(Token::EOI) {
+            return Err(self.unexpected("'func'"));
         }
-        if self.peek(Token::EOI) {
-            self.expect(Token::EOI);
-            self.tree = ParseTree::new(Token::EOI);
+        self.tree = ParseTree::new(Token::EOI);
 
 
-            self.tree.print();
-            let rcProgram = Rc::new(program);
-            let machine = Machine::new(rcProgram);
-    
-            machine.run();
+        self.tree.print();
 
-            return;
-        }
-        
+        Ok(Rc::new(program))
     }
 
     // parse_func  -> KW_FUNC ID() <parse_parameter_list> ARROW_R ID() <parse_block_nest>
-    fn parse_func(&mut self, mut tree: ParseTree) -> ParseTree {
+    fn parse_func(&mut self, mut tree: ParseTree) -> Result<ParseTree, ParseError> {
         {
-            self.advance();
-            tree.push(self.parse_parameter_list());
+            self.advance()?;
+            tree.push(self.parse_parameter_list()?);
             if self.peek(Token::ARROW_R) {
-                tree.push(self.expect(Token::ARROW_R));
-                tree.push(self.expect(Token::TYPE_I32));
+                tree.push(self.expect(Token::ARROW_R)?);
+                tree.push(self.parse_type()?);
             }
-            tree.push(self.parse_block_nest());
+            tree.push(self.parse_block_nest()?);
         }
-        return tree;
+        return Ok(tree);
     }
 
     // parse_parameter_list -> PAREN_L PAREN_R | PAREN_L parse_parameter [(COMMA <parse_parameter>) repeats until no COMMA found] PAREN_R
-    fn parse_parameter_list(&mut self) -> ParseTree{
-        let mut output = ParseTree::new(self.curr());
-        self.advance();
-        output.push(self.expect(Token::PAREN_L));
+    fn parse_parameter_list(&mut self) -> Result<ParseTree, ParseError> {
+        let mut output = self.expect(Token::id())?;
+        output.push(self.expect(Token::PAREN_L)?);
         if self.peek(Token::PAREN_R) {
-            output.push(self.expect(Token::PAREN_R));
-            return output;
+            output.push(self.expect(Token::PAREN_R)?);
+            return Ok(output);
         }
-        output.push(self.parse_parameter());
-        while self.accept(Token::COMMA) {
-            output.push(self.parse_parameter());
+        output.push(self.parse_parameter()?);
+        while self.accept(Token::COMMA)? {
+            output.push(self.parse_parameter()?);
         }
-        output.push(self.expect(Token::PAREN_R));
-        return output;
+        output.push(self.expect(Token::PAREN_R)?);
+        return Ok(output);
     }
 
-    // parse_parameter -> ID() COLON <TYPE_I32 | TYPE_CHAR | TYPE_F32>  [ Complete, there are no nonTerminals remaining ]
-    fn parse_parameter(&mut self) -> ParseTree{
+    // parse_parameter -> ID() [COLON <parse_type>]  [ Complete, there are no nonTerminals remaining ]
+    fn parse_parameter(&mut self) -> Result<ParseTree, ParseError> {
         let mut output = ParseTree::new(self.curr());
-        output.push(self.expect(Token::id()));
+        output.push(self.expect(Token::id())?);
 
         if (self.peek(Token::COLON)){
-            output.push(self.expect(Token::COLON));
-            if self.peek(Token::TYPE_I32) {
-                output.push(self.expect(Token::TYPE_I32));
-            }
-            if self.peek(Token::TYPE_F32) {
-                output.push(self.expect(Token::TYPE_F32));
-            }
-            if self.peek(Token::TYPE_CHAR) {
-                output.push(self.expect(Token::TYPE_CHAR));
-            }
+            output.push(self.expect(Token::COLON)?);
+            output.push(self.parse_type()?);
         }
-        return output;
+        return Ok(output);
+    }
+
+    // parse_type -> <TYPE_I32 | TYPE_I64 | TYPE_U8 | TYPE_U32 | TYPE_U64 | TYPE_F32 | TYPE_F64 | TYPE_CHAR | TYPE_BOOL | TYPE_STRING>
+    fn parse_type(&mut self) -> Result<ParseTree, ParseError> {
+        let token = self.curr();
+        if !token.is_type() {
+            return Err(self.unexpected("a type"));
+        }
+        self.advance()?;
+        Ok(ParseTree::new(token))
     }
 
     // parse_block_nest -> BRACKET_L BRACKET_R | BRACKET_L <parse_block_list, parse_if, parse_while, parse_else, parse_statement, parse_print, parse_expression> BRACKET_R
-    fn parse_block_nest(&mut self) -> ParseTree{
-        let mut output = ParseTree::new(self.curr());
-        self.advance();
+    fn parse_block_nest(&mut self) -> Result<ParseTree, ParseError> {
+        let mut output = self.expect(Token::BRACKET_L)?;
         while ! self.peek(Token::BRACKET_R) {
             if self.peek(Token::BRACKET_L) {
-                output.push(self.parse_block_list());
+                output.push(self.parse_block_list()?);
             }
-            if self.peek(Token::IF) {
-                output.push(self.parse_if());
+            else if self.peek(Token::IF) {
+                output.push(self.parse_if()?);
                 while self.peek(Token::ELSE) {
-                    output.push(self.parse_else());
+                    output.push(self.parse_else()?);
                 }
             }
-            if self.peek(Token::WHILE) {
-                output.push(self.parse_while());
+            else if self.peek(Token::WHILE) {
+                output.push(self.parse_while()?);
             }
-            if self.peek(Token::LET) | self.peek(Token::RETURN){
-                output.push(self.parse_statement());
-                self.expect(Token::SEMICOLON);
+            else if self.peek(Token::LET) | self.peek(Token::RETURN){
+                output.push(self.parse_statement()?);
+                self.expect(Token::SEMICOLON)?;
+            }
+            else if self.peek(Token::PRINT) {
+                output.push(self.parse_print()?);
+                self.expect(Token::SEMICOLON)?;
             }
-            if self.peek(Token::PRINT) {
-                output.push(self.parse_print());
-                self.expect(Token::SEMICOLON);
+            else if self.peek(Token::BREAK) | self.peek(Token::CONTINUE) {
+                output.push(self.parse_jump()?);
+                self.expect(Token::SEMICOLON)?;
+            }
+            else if self.peek(Token::id()) {
+                output.push(self.parse_expression()?);//IAN:Removed false from params
+                self.expect(Token::SEMICOLON)?;
             }
-            if self.peek(Token::id()) {
-                output.push(self.parse_expression());//IAN:Removed false from params
-                self.expect(Token::SEMICOLON);
+            else {
+                return Err(self.unexpected("a statement"));
             }
         }
-        output.push(self.expect(Token::BRACKET_R));
+        output.push(self.expect(Token::BRACKET_R)?);
 
-        return output;
+        return Ok(output);
     }
 
     // parse_block_list -> <parse_block_nest> | <parse_block_nest> <parse_block_list>
-    fn parse_block_list(&mut self) -> ParseTree{
-        let mut output = self.parse_block_nest();
+    fn parse_block_list(&mut self) -> Result<ParseTree, ParseError> {
+        let mut output = self.parse_block_nest()?;
         if self.peek(Token::BRACKET_L) {
-            output.push(self.parse_block_list());
+            output.push(self.parse_block_list()?);
         }
-        return output;
+        return Ok(output);
     }
 
     // parse_expression -> (uses pratt parser to read expression)
-    fn parse_expression(&mut self) -> ParseTree{
+    fn parse_expression(&mut self) -> Result<ParseTree, ParseError> {
+        let line = self.lexer.line();
         let mut token_string : String = "".to_string();
         
-        while (self.curr() != Token::SEMICOLON) & (self.curr() != Token::BRACKET_L){
+        while (self.curr() != Token::SEMICOLON) & (self.curr() != Token::BRACKET_L) & (self.curr() != Token::EOI){
             token_string.push_str(&(self.curr().string().to_string() + " "));
-            self.advance();
+            self.advance()?;
+        }
+        if token_string.is_empty() {
+            return Err(self.unexpected("an expression"));
         }
         let token_str : &'static str = token_string.leak();
 
-        let prattlexer = Lexer::new(token_str);
+        let prattlexer = Lexer::starting_at(token_str, line)?;
         let mut prattparser = PrattParser::new(prattlexer);
-        let mut output =  prattparser.analyze();
+        let mut output =  prattparser.analyze()?;
+        output.set_line(line);
 
-        return output;
+        return Ok(output);
 
     }
 
     // parse_if -> IF <parse_expression> BRACKET_L <parse_statement, parse_if, parse_print, parse_while, parse_expression> BRACKET_R
-    fn parse_if(&mut self) -> ParseTree{
+    fn parse_if(&mut self) -> Result<ParseTree, ParseError> {
         let mut output = ParseTree::new(self.curr());
-        self.advance();
-        output.push(self.parse_expression());
-        output.push(self.expect(Token::BRACKET_L));
+        self.advance()?;
+        output.push(self.parse_expression()?);
+        output.push(self.expect(Token::BRACKET_L)?);
         while ! self.peek(Token::BRACKET_R) {
             if self.peek(Token::LET) | self.peek(Token::RETURN){
-                output.push(self.parse_statement());
-                self.expect(Token::SEMICOLON);
+                output.push(self.parse_statement()?);
+                self.expect(Token::SEMICOLON)?;
             }
             else if self.peek(Token::IF) {
-                output.push(self.parse_if());
+                output.push(self.parse_if()?);
                 while self.peek(Token::ELSE) {
-                    output.push(self.parse_else());
+                    output.push(self.parse_else()?);
                 }
             }
             else if self.peek(Token::PRINT) {
-                output.push(self.parse_print());//
-                self.expect(Token::SEMICOLON);
+                output.push(self.parse_print()?);//
+                self.expect(Token::SEMICOLON)?;
             }
             else if self.peek(Token::WHILE) {
-                output.push(self.parse_while());
+                output.push(self.parse_while()?);
+            }
+            else if self.peek(Token::BREAK) | self.peek(Token::CONTINUE) {
+                output.push(self.parse_jump()?);
+                self.expect(Token::SEMICOLON)?;
             }
             else if self.peek(Token::id()) {
-                output.push(self.parse_expression());
-                self.expect(Token::SEMICOLON);
+                output.push(self.parse_expression()?);
+                self.expect(Token::SEMICOLON)?;
+            }
+            else {
+                return Err(self.unexpected("a statement"));
             }
         }
-        output.push(self.expect(Token::BRACKET_R));
+        output.push(self.expect(Token::BRACKET_R)?);
         if(self.peek(Token::ELSE)){
-            output.push(self.parse_else());
+            output.push(self.parse_else()?);
         }
 
-        return output;
+        return Ok(output);
     }
 
 
     // parse_while -> WHILE LIT_BOOL() BRACKETL <parse_expression> BRACKET_R
-    fn parse_while(&mut self) -> ParseTree{
+    fn parse_while(&mut self) -> Result<ParseTree, ParseError> {
         let mut output = ParseTree::new(self.curr());
-        self.advance();
-        output.push(self.parse_expression());
-        output.push(self.expect(Token::BRACKET_L));
+        self.advance()?;
+        output.push(self.parse_expression()?);
+        output.push(self.expect(Token::BRACKET_L)?);
         while ! self.peek(Token::BRACKET_R) {
             if self.peek(Token::LET) | self.peek(Token::RETURN){
-                output.push(self.parse_statement());
-                self.expect(Token::SEMICOLON);
+                output.push(self.parse_statement()?);
+                self.expect(Token::SEMICOLON)?;
             }
             else if self.peek(Token::WHILE) {
-                output.push(self.parse_while());
+                output.push(self.parse_while()?);
             }
             else if self.peek(Token::IF) {
-                output.push(self.parse_if());
+                output.push(self.parse_if()?);
                 while self.peek(Token::ELSE) {
-                    output.push(self.parse_else());
+                    output.push(self.parse_else()?);
                 }
             }
             else if self.peek(Token::PRINT) {
-                output.push(self.parse_print());
-                self.expect(Token::SEMICOLON);
+                output.push(self.parse_print()?);
+                self.expect(Token::SEMICOLON)?;
+            }
+            else if self.peek(Token::BREAK) | self.peek(Token::CONTINUE) {
+                output.push(self.parse_jump()?);
+                self.expect(Token::SEMICOLON)?;
             }
             else if self.peek(Token::id()) {
-                output.push(self.parse_expression());
-                self.expect(Token::SEMICOLON);
+                output.push(self.parse_expression()?);
+                self.expect(Token::SEMICOLON)?;
+            }
+            else {
+                return Err(self.unexpected("a statement"));
             }
         }
-        output.push(self.expect(Token::BRACKET_R));
+        output.push(self.expect(Token::BRACKET_R)?);
 
-        return output;
+        return Ok(output);
     }
 
     // parse_else -> ELSE <parse_if> | ELSE BRACKET_L <parse_let | parse_return | parse_if> BRACKET_R
-    fn parse_else(&mut self) -> ParseTree{
+    fn parse_else(&mut self) -> Result<ParseTree, ParseError> {
         let mut output = ParseTree::new(self.curr());
-        self.advance();
+        self.advance()?;
 
         if self.peek(Token::IF) {
-            output.push(self.parse_if()); //
+            output.push(self.parse_if()?); //
         }
         else {
-            output.push(self.expect(Token::BRACKET_L));
+            output.push(self.expect(Token::BRACKET_L)?);
             while ! self.peek(Token::BRACKET_R) {
                 if self.peek(Token::LET) | self.peek(Token::RETURN){
-                    output.push(self.parse_statement());
-                    self.expect(Token::SEMICOLON);
+                    output.push(self.parse_statement()?);
+                    self.expect(Token::SEMICOLON)?;
                 }
                 else if self.peek(Token::WHILE) {
-                    output.push(self.parse_while());
+                    output.push(self.parse_while()?);
                 }
                 else if self.peek(Token::IF) {
-                    output.push(self.parse_if());
+                    output.push(self.parse_if()?);
                     while self.peek(Token::ELSE) {
-                        output.push(self.parse_else());
+                        output.push(self.parse_else()?);
                     }
                 }
                 else if self.peek(Token::PRINT) {
-                    output.push(self.parse_print());
-                    self.expect(Token::SEMICOLON);
+                    output.push(self.parse_print()?);
+                    self.expect(Token::SEMICOLON)?;
+                }
+                else if self.peek(Token::BREAK) | self.peek(Token::CONTINUE) {
+                    output.push(self.parse_jump()?);
+                    self.expect(Token::SEMICOLON)?;
                 }
                 else if self.peek(Token::id()) {
-                    output.push(self.parse_expression());
-                    self.expect(Token::SEMICOLON);
+                    output.push(self.parse_expression()?);
+                    self.expect(Token::SEMICOLON)?;
+                }
+                else {
+                    return Err(self.unexpected("a statement"));
                 }
             }
-            output.push(self.expect(Token::BRACKET_R));
+            output.push(self.expect(Token::BRACKET_R)?);
         }
-        return output;
+        return Ok(output);
     }
 
     // parse_statement -> RETURN <parse_expression> SEMICOLON
-    fn parse_statement(&mut self) -> ParseTree{
+    fn parse_statement(&mut self) -> Result<ParseTree, ParseError> {
         let mut output = ParseTree::new(self.curr());
-        self.advance();
-        output.push(self.parse_expression());
-        return output;
+        self.advance()?;
+        output.push(self.parse_expression()?);
+        return Ok(output);
     }
 
-    // parse_print -> PRINT parse_expression SEMICOLON
-    fn parse_print(&mut self) -> ParseTree{
+    // parse_jump -> BREAK SEMICOLON | CONTINUE SEMICOLON
+    fn parse_jump(&mut self) -> Result<ParseTree, ParseError> {
+        let output = ParseTree::new(self.curr());
+        self.advance()?;
+        Ok(output)
+    }
+
+    // parse_print -> PRINT parse_argument [(COMMA <parse_argument>) repeats until no COMMA found] SEMICOLON
+    fn parse_print(&mut self) -> Result<ParseTree, ParseError> {
         let mut output = ParseTree::new(self.curr());
-        self.advance();
-        output.push(self.parse_expression());
-        return output;
+        self.advance()?;
+        output.push(self.parse_argument()?);
+        while self.accept(Token::COMMA)? {
+            output.push(self.parse_argument()?);
+        }
+        return Ok(output);
+    }
+
+    // parse_argument -> (like parse_expression, but also ends at a COMMA outside of parentheses)
+    fn parse_argument(&mut self) -> Result<ParseTree, ParseError> {
+        let line = self.lexer.line();
+        let mut token_string : String = "".to_string();
+        let mut depth = 0;
+
+        while (self.curr() != Token::SEMICOLON) & (self.curr() != Token::EOI) & !((self.curr() == Token::COMMA) & (depth == 0)) {
+            if self.curr() == Token::PAREN_L {
+                depth += 1;
+            }
+            if self.curr() == Token::PAREN_R {
+                depth -= 1;
+            }
+            token_string.push_str(&(self.curr().string().to_string() + " "));
+            self.advance()?;
+        }
+        if token_string.is_empty() {
+            return Err(self.unexpected("an expression"));
+        }
+        let token_str : &'static str = token_string.leak();
+
+        let prattlexer = Lexer::starting_at(token_str, line)?;
+        let mut prattparser = PrattParser::new(prattlexer);
+        let mut output =  prattparser.analyze()?;
+        output.set_line(line);
+
+        return Ok(output);
     }
 }
 
@@ -707,8 +774,8 @@ impl DescentParser { // utility functions for lexer
         self.lexer.current()
     }
 
-    fn advance(&mut self) {
-        self.lexer.advance();
+    fn advance(&mut self) -> Result<(), ParseError> {
+        self.lexer.advance()
     }
 
     // called to check that next symbol is the correct symbol,
@@ -716,33 +783,37 @@ impl DescentParser { // utility functions for lexer
     // remember that the self.curr is not the actual current token, but is instead the one coming next
     // when initialized, current token is first token
     // tokens with a stored value inside are considered equivalent to the same token type, regardless of inner value
-    fn expect(&mut self, symbol: Token) -> ParseTree{
+    fn expect(&mut self, symbol: Token) -> Result<ParseTree, ParseError> {
         let output: ParseTree;
         if self.curr() == symbol {
             output = ParseTree::new(self.curr());
-            self.advance();
+            self.advance()?;
         } else {
 
-            println!("{:?}", self.curr());
-            println!("{:?}", symbol);
+            debug!("expected {:?}, found {:?}", symbol, self.curr());
 
-            panic!("Did not expect {:?}!", self.curr());
+            return Err(self.unexpected(symbol.expected()));
         }
 
-        return output;
+        return Ok(output);
     }
 
-    // similar to expect, but doesnt crash if expected symbol is not found
+    // similar to expect, but doesnt fail if expected symbol is not found
     // returns boolean
-    fn accept(&mut self, symbol: Token) -> bool {
+    fn accept(&mut self, symbol: Token) -> Result<bool, ParseError> {
         if self.curr() == symbol {
-            self.advance();
-            true
+            self.advance()?;
+            Ok(true)
         } else {
-            false
+            Ok(false)
         }
     }
 
+    // error for a token that cannot appear here
+    fn unexpected(&mut self, expected: &'static str) -> ParseError {
+        ParseError::Unexpected { expected, found: self.curr().described(), line: self.lexer.line() }
+    }
+
     // checks that current symbol is equivalent to a given symbol
     // used for block lists
     fn peek(&mut self, symbol: Token) -> bool {
@@ -754,7 +825,7 @@ impl DescentParser { // utility functions for lexer
 impl DescentParser { 
 // prints self at correctly indented location
 fn indent_print(&mut self, msg: &'static str) {
-    println!("{:<indent$}{:}", "", msg, indent=self.indent);
+    trace!("{:<indent$}{:}", "", msg, indent=self.indent);
 }
 
     // self explanatory
@@ -766,77 +837,3 @@ fn indent_print(&mut self, msg: &'static str) {
     }
 
 }
-pub fn main() {
-
-    let file_path = "";
-    let contents = fs::read_to_string(file_path)
-        .expect("Should have been able to read the file");
-
-    // create a sequence of tokens that is assumed to
-    //   be output of the lexer
-    let tokens = contents;
-
-    // create Pratt parser
-    let lexer = Lexer::new(&tokens);
-    let mut parser = PrattParser::new(lexer);
-
-    // start Pratt top-down operator precedence parsing
-    let tree = parser.analyze();
-
-    // print parse tree
-    tree.print();
-
-    // create a sequence of tokens that is assumed to
-    //   be output of the lexer
-
-    // create input for lexer
-
-    /*
-    let input = r#"
-    func add(x : int32, y : char, z : f32) -> int32
-    [
-        let value : int32 = 35;
-        if true [
-            let a = 5;
-    ]
-        else [
-            let b = 7;
-    ]
-        return value;
-    ]
-
-    func repeat() -> i32 [
-        while [
-            if (a and b) [
-                print("both are true");
-            ]
-            else if (a) [
-                print("b is false");
-            ]
-            else [
-                print("a is false");
-                x = x + 5;
-            ]
-        ]
-
-        return 3;
-    ]
-
-    func another(y : char) [
-        print(y);
-    ]
-
-    func main()
-    [
-        let sum : int32 = 42;
-        return sum;
-    ]
-    "#;
-     */
-
-    // create recursive descent parser
-    let lexer = Lexer::new(&tokens);
-    let mut parser = DescentParser::new(lexer);
-
-    parser.analyze();
-}
\ No newline at end of file
@@ -0,0 +1,122 @@
+use std::cell::Cell;
+use std::ops::Deref;
+use std::rc::Rc;
+use log::info;
+use crate::error::AnalysisError;
+use crate::frame::Address;
+use crate::interner::Symbol;
+use crate::symbols::Symbols;
+use crate::tree::{BlockNode, ExprNode, ProgramNode, StmtNode};
+
+/// Turns every variable and call name into a `(depth, slot)` frame address.
+/// Runs after the analyzer has collected the symbol tables.
+/// Also rejects `break` and `continue` outside of a loop, and calls with the wrong number of arguments.
+pub struct Resolver {
+    program: Rc<ProgramNode>,
+}
+
+impl Resolver {
+
+    pub fn new(program: Rc<ProgramNode>) -> Resolver {
+        Resolver { program }
+    }
+
+    pub fn resolve(&self) -> Result<(), AnalysisError> {
+        info!("Resolve.");
+        for rc_func in &self.program.func_nodes {
+            let rc_symbols = rc_func.block_node.symbols.clone();
+            let symbols = rc_symbols.borrow();
+            Self::resolve_block(&rc_func.block_node, &symbols, rc_func.name, 0)?;
+        }
+        Ok(())
+    }
+
+    fn resolve_block(block: &BlockNode, symbols: &Symbols, func_name: Symbol, loop_depth: usize) -> Result<(), AnalysisError> {
+        for rc_stmt in &block.statements {
+            match rc_stmt.deref() {
+                StmtNode::Let(_) => {}
+                StmtNode::Assign(assign) => {
+                    Self::resolve_name(assign.name, &assign.address, symbols, func_name)?;
+                    assign.kind.set(symbols.declared(assign.name));
+                    Self::resolve_expr(&assign.expr, symbols, func_name)?;
+                }
+                StmtNode::If(ifNode) => {
+                    Self::resolve_expr(&ifNode.cond, symbols, func_name)?;
+                    Self::resolve_block(&ifNode.block_node_true, symbols, func_name, loop_depth)?;
+                    Self::resolve_block(&ifNode.block_node_false, symbols, func_name, loop_depth)?;
+                }
+                StmtNode::While(whileNode) => {
+                    Self::resolve_expr(&whileNode.cond, symbols, func_name)?;
+                    Self::resolve_block(&whileNode.block_node_true, symbols, func_name, loop_depth + 1)?;
+                }
+                StmtNode::Break | StmtNode::Continue => {
+                    if loop_depth == 0 {
+                        return Err(AnalysisError::LoopControlOutsideLoop(func_name));
+                    }
+                }
+                StmtNode::Return(ret) => {
+                    Self::resolve_expr(&ret.expr, symbols, func_name)?;
+                }
+                StmtNode::Print(print) => {
+                    for rc_expr in &print.exprs {
+                        Self::resolve_expr(rc_expr, symbols, func_name)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve_expr(expr: &ExprNode, symbols: &Symbols, func_name: Symbol) -> Result<(), AnalysisError> {
+        match expr {
+            ExprNode::Var(name, address) => {
+                Self::resolve_name(*name, address, symbols, func_name)?;
+            }
+            ExprNode::Val(_) => {}
+            ExprNode::Add(expr_a, expr_b)
+            | ExprNode::Sub(expr_a, expr_b)
+            | ExprNode::Mul(expr_a, expr_b)
+            | ExprNode::Div(expr_a, expr_b)
+            | ExprNode::Equal(expr_a, expr_b)
+            | ExprNode::LessThan(expr_a, expr_b)
+            | ExprNode::GreaterThan(expr_a, expr_b)
+            | ExprNode::NotEqual(expr_a, expr_b)
+            | ExprNode::LessThanEqual(expr_a, expr_b)
+            | ExprNode::GreaterThanEqual(expr_a, expr_b)
+            | ExprNode::And(expr_a, expr_b)
+            | ExprNode::Or(expr_a, expr_b) => {
+                Self::resolve_expr(expr_a, symbols, func_name)?;
+                Self::resolve_expr(expr_b, symbols, func_name)?;
+            }
+            ExprNode::Not(expr_a) | ExprNode::Cast(expr_a, _) => {
+                Self::resolve_expr(expr_a, symbols, func_name)?;
+            }
+            ExprNode::Call(name, address, rc_exprs, _) => {
+                Self::resolve_name(*name, address, symbols, func_name)?;
+                // calls through a variable are only checked when they happen
+                if let Some(expected) = symbols.arity(*name) {
+                    if expected != rc_exprs.len() {
+                        return Err(AnalysisError::ArityMismatch {
+                            function: *name,
+                            expected,
+                            found: rc_exprs.len(),
+                            caller: func_name,
+                        });
+                    }
+                }
+                for rc_expr in rc_exprs {
+                    Self::resolve_expr(rc_expr, symbols, func_name)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve_name(name: Symbol, address: &Cell<Option<Address>>, symbols: &Symbols, func_name: Symbol) -> Result<(), AnalysisError> {
+        match symbols.resolve(name) {
+            Some(resolved) => { address.set(Some(resolved)); Ok(()) }
+            None => { Err(AnalysisError::UndeclaredIdentifier { name, function: func_name }) }
+        }
+    }
+}
+
@@ -0,0 +1,211 @@
+use std::cell::{RefCell, RefMut};
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use clap::ValueEnum;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use crate::builtins;
+use crate::error::{Error, RuntimeError};
+use crate::native::Native;
+use crate::value::Value;
+
+/// What integer arithmetic does when the result does not fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OverflowMode {
+    /// Continue with an arbitrary-precision integer; other widths than i32 trap.
+    #[default]
+    Promote,
+    /// Stop with a runtime error.
+    Trap,
+    /// Wrap around in two's complement.
+    Wrap,
+    /// Clamp to the smallest or largest value.
+    Saturate,
+}
+
+/// Whether strings and numbers mix in arithmetic and comparisons.
+/// See `Evaluator::coerce` for the full table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Coercion {
+    /// Reject arithmetic and comparisons between strings and numbers.
+    #[default]
+    Strict,
+    /// Parse the string as a number.
+    Lenient,
+}
+
+/// Settings shared by the executor and evaluator for one run of a program,
+/// and the sinks its output goes to.
+pub struct Runtime {
+    pub overflow: OverflowMode,
+    pub coercion: Coercion,
+    // shared by reference during a run, so writes go through a RefCell
+    input: RefCell<Box<dyn BufRead>>,
+    output: RefCell<Box<dyn Write>>,
+    diagnostics: RefCell<Box<dyn Write>>,
+    natives: Vec<Rc<Native>>,
+    rng: RefCell<StdRng>,
+    files: Option<PathBuf>,
+    arguments: Vec<String>,
+}
+
+impl Runtime {
+
+    /// Input comes from stdin, program output goes to stdout and error reports to stderr.
+    /// The builtin prelude is already registered.
+    pub fn new() -> Runtime {
+        let mut runtime = Runtime {
+            overflow: OverflowMode::default(),
+            coercion: Coercion::default(),
+            input: RefCell::new(Box::new(BufReader::new(io::stdin()))),
+            output: RefCell::new(Box::new(io::stdout())),
+            diagnostics: RefCell::new(Box::new(io::stderr())),
+            natives: vec![],
+            rng: RefCell::new(StdRng::from_entropy()),
+            files: None,
+            arguments: vec![],
+        };
+        builtins::prelude(&mut runtime);
+        runtime
+    }
+
+    /// Makes a Rust function callable from the program. It replaces an earlier native
+    /// of the same name, and is itself hidden by a program function of that name.
+    pub fn register<F>(&mut self, name: &str, arity: usize, function: F)
+    where
+        F: Fn(&Runtime, Vec<Value>) -> Result<Value, RuntimeError> + 'static,
+    {
+        let native = Rc::new(Native::new(name, arity, function));
+        self.natives.retain(|other| other.name != native.name);
+        self.natives.push(native);
+    }
+
+    pub fn natives(&self) -> &[Rc<Native>] {
+        &self.natives
+    }
+
+    /// Makes the random builtins repeat the same sequence on every run.
+    /// Without a seed they start from system entropy.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = RefCell::new(StdRng::seed_from_u64(seed));
+    }
+
+    pub fn rng(&self) -> RefMut<'_, StdRng> {
+        self.rng.borrow_mut()
+    }
+
+    /// Program arguments, handed to `main` and returned by `args()`.
+    pub fn set_arguments(&mut self, arguments: Vec<String>) {
+        self.arguments = arguments;
+    }
+
+    pub fn arguments(&self) -> &[String] {
+        &self.arguments
+    }
+
+    /// Lets the file builtins use `dir` and everything below it. File access is off until then.
+    pub fn allow_files(&mut self, dir: &Path) -> io::Result<()> {
+        self.files = Some(dir.canonicalize()?);
+        Ok(())
+    }
+
+    /// Where `path`, taken relative to the allowed directory, really is,
+    /// or an error when file access is off or the path leads outside the directory.
+    pub fn sandboxed(&self, path: &str) -> Result<PathBuf, RuntimeError> {
+        let denied = |reason| RuntimeError::FileAccessDenied { path: path.to_string(), reason };
+        let root = self.files.as_ref().ok_or(denied("file access is off"))?;
+        let candidate = root.join(path);
+        // symlinks and `..` are resolved before the check; a file that does not
+        // exist yet is checked through its directory
+        let resolved = match candidate.canonicalize() {
+            Ok(resolved) => { resolved }
+            Err(_) => {
+                let name = candidate.file_name().ok_or(denied("not a file name"))?;
+                let parent = candidate.parent().and_then(|parent| parent.canonicalize().ok());
+                let resolved = parent.ok_or(denied("no such directory"))?.join(name);
+                // something is there but cannot be resolved: a symlink to a missing
+                // target, which creating the file would follow wherever it points
+                if fs::symlink_metadata(&resolved).is_ok() {
+                    return Err(denied("a symbolic link to a missing file"));
+                }
+                resolved
+            }
+        };
+        if resolved.starts_with(root) {
+            Ok(resolved)
+        } else {
+            Err(denied("outside the allowed directory"))
+        }
+    }
+
+    /// Source for the input builtins.
+    pub fn set_input(&mut self, input: Box<dyn BufRead>) {
+        self.input = RefCell::new(input);
+    }
+
+    /// Sink for the program's `print` statements.
+    pub fn set_output(&mut self, output: Box<dyn Write>) {
+        self.output = RefCell::new(output);
+    }
+
+    /// Sink for runtime error reports.
+    pub fn set_diagnostics(&mut self, diagnostics: Box<dyn Write>) {
+        self.diagnostics = RefCell::new(diagnostics);
+    }
+
+    pub fn print(&self, line: &str) -> Result<(), RuntimeError> {
+        writeln!(self.output.borrow_mut(), "{line}")?;
+        Ok(())
+    }
+
+    /// Next line of input without its line ending, or `None` once the input is used up.
+    /// Output is flushed first, so a prompt shows before the program waits.
+    pub fn read_line(&self) -> Result<Option<String>, RuntimeError> {
+        self.flush()?;
+        let mut line = String::new();
+        if self.input.borrow_mut().read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let length = line.trim_end_matches(['\n', '\r']).len();
+        line.truncate(length);
+        Ok(Some(line))
+    }
+
+    pub fn end_of_input(&self) -> Result<bool, RuntimeError> {
+        Ok(self.input.borrow_mut().fill_buf()?.is_empty())
+    }
+
+    pub fn flush(&self) -> Result<(), RuntimeError> {
+        self.output.borrow_mut().flush()?;
+        Ok(())
+    }
+
+    /// Writes the error and, for runtime errors, the call stack that led to it.
+    pub fn report(&self, error: &Error) {
+        let mut diagnostics = self.diagnostics.borrow_mut();
+        // nowhere left to report a failing diagnostics sink, so its errors are dropped
+        writeln!(diagnostics, "{error}").ok();
+        for call_site in error.stack_trace() {
+            writeln!(diagnostics, "    in {call_site}").ok();
+        }
+        diagnostics.flush().ok();
+    }
+}
+
+impl Default for Runtime {
+    fn default() -> Runtime {
+        Runtime::new()
+    }
+}
+
+impl fmt::Debug for Runtime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Runtime")
+            .field("overflow", &self.overflow)
+            .field("coercion", &self.coercion)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,25 +1,29 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
-use crate::value::Value;
+use crate::frame::Address;
+use crate::interner::Symbol;
+use crate::value::{Type, Value};
 
 #[derive(Debug, Clone)]
-pub struct Symbol {
-    pub name: String,
+pub struct SymbolEntry {
+    pub name: Symbol,
     pub value: Value,
     pub signature: usize,
+    pub slot: usize,
+    pub kind: Option<Type>,
 }
 
-impl Symbol {
-    pub fn new(name: String, value: Value, signature: usize) -> Symbol {
-        Symbol { name, value, signature }
+impl SymbolEntry {
+    pub fn new(name: Symbol, value: Value, signature: usize, slot: usize, kind: Option<Type>) -> SymbolEntry {
+        SymbolEntry { name, value, signature, slot, kind }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct Symbols {
     pub parent: Option<Rc<RefCell<Symbols>>>,
-    pub map: HashMap<String, Symbol>,
+    pub map: HashMap<Symbol, SymbolEntry>,
 }
 
 impl Symbols {
@@ -31,4 +35,34 @@ impl Symbols {
         }
     }
 
+    // finds the frame address of a name, counting one frame per parent table walked
+    pub fn resolve(&self, name: Symbol) -> Option<Address> {
+        match self.map.get(&name) {
+            Some(symbol) => { Some(Address::new(0, symbol.slot)) }
+            None => {
+                let rc_parent = self.parent.as_ref()?;
+                let address = rc_parent.borrow().resolve(name)?;
+                Some(Address::new(address.depth + 1, address.slot))
+            }
+        }
+    }
+
+    // declared type of a parameter or let in this table
+    pub fn declared(&self, name: Symbol) -> Option<Type> {
+        self.map.get(&name).and_then(|symbol| symbol.kind)
+    }
+
+    // number of parameters of the function a name resolves to, or None for a variable
+    pub fn arity(&self, name: Symbol) -> Option<usize> {
+        match self.map.get(&name) {
+            Some(symbol) => {
+                match symbol.value {
+                    Value::Func(_, _) | Value::Native(_) => { Some(symbol.signature) }
+                    _ => { None }
+                }
+            }
+            None => { self.parent.as_ref()?.borrow().arity(name) }
+        }
+    }
+
 }
\ No newline at end of file
@@ -2,6 +2,7 @@
 #![allow(dead_code)]
 
 use std::mem::discriminant;
+use crate::interner::Symbol;
 
 #[derive(Debug, Clone)]
 pub enum Token {
@@ -17,14 +18,16 @@ pub enum Token {
     OP_NOT, OP_AND, OP_OR,
     // Assignment:
     OP_ASSIGN,
+    // Conversion:
+    OP_AS,
     // Keywords:
-    KW_FUNC, LET, IF, ELSE, WHILE, PRINT, RETURN,
+    KW_FUNC, LET, IF, ELSE, WHILE, PRINT, RETURN, BREAK, CONTINUE,
     // Identifiers:
-    ID(String),
+    ID(Symbol),
     // Basic Types:
-    TYPE_I32, TYPE_F32, TYPE_CHAR,
+    TYPE_I32, TYPE_I64, TYPE_U8, TYPE_U32, TYPE_U64, TYPE_F32, TYPE_F64, TYPE_CHAR, TYPE_BOOL, TYPE_STRING,
     // Literals:
-    LIT_I32(i32), LIT_F32(f32), LIT_CHAR(char), LIT_STRING(String), LIT_BOOL(bool), // LIT_BOOL added for if-else statements
+    LIT_I32(i32), LIT_I64(i64), LIT_U8(u8), LIT_U32(u32), LIT_U64(u64), LIT_BIGINT(String), LIT_F32(f32), LIT_F64(f64), LIT_CHAR(char), LIT_STRING(String), LIT_BOOL(bool), // LIT_BOOL added for if-else statements
     // End-of-Input:
     EOI
 }
@@ -40,12 +43,18 @@ impl Eq for Token { }
 
 impl Token {
     pub fn id() -> Token {
-        Token::ID(String::new())
+        Token::ID(Symbol::intern(""))
     }
     pub fn lit_i32() -> Token { Token::LIT_I32(0) }
     pub fn lit_f32() -> Token { Token::LIT_F32(0.0) }
     pub fn bool() -> Token {Token::LIT_BOOL(true)}
 
+    pub fn is_type(&self) -> bool {
+        matches!(self, Token::TYPE_I32 | Token::TYPE_I64 | Token::TYPE_U8 | Token::TYPE_U32
+            | Token::TYPE_U64 | Token::TYPE_F32 | Token::TYPE_F64 | Token::TYPE_CHAR
+            | Token::TYPE_BOOL | Token::TYPE_STRING)
+    }
+
 
     pub fn string(&self) -> &str{
         match self {
@@ -74,6 +83,7 @@ impl Token {
             Token::OP_AND => "and",
             Token::OP_OR => "or",
             Token::OP_ASSIGN => "=",
+            Token::OP_AS => "as",
             Token::KW_FUNC => "func",
             Token::LET => "let",
             Token::IF => "if",
@@ -81,10 +91,27 @@ impl Token {
             Token::WHILE => "while",
             Token::PRINT => "print",
             Token::RETURN => "return",
+            Token::BREAK => "break",
+            Token::CONTINUE => "continue",
             Token::TYPE_I32 => "i32",
+            Token::TYPE_I64 => "i64",
+            Token::TYPE_U8 => "u8",
+            Token::TYPE_U32 => "u32",
+            Token::TYPE_U64 => "u64",
             Token::TYPE_F32 => "f32",
+            Token::TYPE_F64 => "f64",
             Token::TYPE_CHAR => "char",
+            Token::TYPE_BOOL => "bool",
+            Token::TYPE_STRING => "string",
             Token::EOI => "EOI",
+            Token::ID(symbol) => symbol.as_str(),
+            Token::LIT_BIGINT(digits) => digits,
+            // suffixed so that re-lexing the expression keeps the literal's type
+            Token::LIT_I64(n) => format!("{n}i64").leak(),
+            Token::LIT_U8(n) => format!("{n}u8").leak(),
+            Token::LIT_U32(n) => format!("{n}u32").leak(),
+            Token::LIT_U64(n) => format!("{n}u64").leak(),
+            Token::LIT_F64(n) => format!("{n}f64").leak(),
             _ => {
                 let mut output: String = format!("{:?}", self);
                 if output.contains("LIT_"){
@@ -1,7 +1,9 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use crate::frame::Address;
+use crate::interner::Symbol;
 use crate::symbols::Symbols;
-use crate::value::Value;
+use crate::value::{Type, Value};
 
 #[derive(Debug, Clone)]
 pub struct ProgramNode {
@@ -22,14 +24,14 @@ impl ProgramNode {
 
 #[derive(Debug, Clone)]
 pub struct FuncNode {
-    pub name: String,
+    pub name: Symbol,
     pub parameters: Vec<Parameter>,
     pub block_node: Rc<BlockNode>,
 }
 
 impl FuncNode {
 
-    pub fn new(name: String, parameters: Vec<Parameter>, block_node: BlockNode) -> FuncNode {
+    pub fn new(name: Symbol, parameters: Vec<Parameter>, block_node: BlockNode) -> FuncNode {
         FuncNode {
             name,
             parameters,
@@ -44,13 +46,15 @@ impl FuncNode {
 
 #[derive(Debug, Clone)]
 pub struct Parameter {
-    pub name: String,
+    pub name: Symbol,
+    pub kind: Option<Type>,
 }
 
 impl Parameter {
-    pub fn new(name: String) -> Parameter {
+    pub fn new(name: Symbol, kind: Option<Type>) -> Parameter {
         Parameter {
-            name
+            name,
+            kind,
         }
     }
 }
@@ -78,34 +82,43 @@ pub enum StmtNode {
     Return(ReturnNode),
     Print(PrintNode),
     While(WhileNode),
+    Break,
+    Continue,
 }
 
 
 #[derive(Debug, Clone)]
 pub struct LetNode {
-    pub name: String,
+    pub name: Symbol,
     pub value: Value,
+    pub kind: Option<Type>,
 }
 
 impl LetNode {
-    pub fn new(name: String, value: Value) -> LetNode {
+    pub fn new(name: Symbol, value: Value, kind: Option<Type>) -> LetNode {
         LetNode {
             name,
             value,
+            kind,
         }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct AssignNode {
-    pub name: String,
+    pub name: Symbol,
+    pub address: Cell<Option<Address>>,
+    // declared type of the target, filled in by the resolver
+    pub kind: Cell<Option<Type>>,
     pub expr: Rc<ExprNode>,
 }
 
 impl AssignNode {
-    pub fn new(name: String, expr: ExprNode) -> AssignNode {
+    pub fn new(name: Symbol, expr: ExprNode) -> AssignNode {
         AssignNode {
             name,
+            address: Cell::new(None),
+            kind: Cell::new(None),
             expr: Rc::new(expr),
         }
     }
@@ -145,13 +158,16 @@ impl WhileNode {
 
 #[derive(Debug, Clone)]
 pub struct PrintNode {
-    pub expr: Rc<ExprNode>,
+    pub exprs: Vec<Rc<ExprNode>>,
+    // the first expression is a string literal whose `{}` placeholders take the others
+    pub template: bool,
 }
 
 impl PrintNode {
-    pub fn new(expr: ExprNode) -> PrintNode {
+    pub fn new(exprs: Vec<ExprNode>, template: bool) -> PrintNode {
         PrintNode {
-            expr: Rc::new(expr),
+            exprs: exprs.into_iter().map(Rc::new).collect(),
+            template,
         }
     }
 }
@@ -171,7 +187,7 @@ impl ReturnNode {
 
 #[derive(Debug, Clone)]
 pub enum ExprNode {
-    Var(String),
+    Var(Symbol, Cell<Option<Address>>),
     Val(Value),
     Add(Rc<ExprNode>, Rc<ExprNode>),
     Sub(Rc<ExprNode>, Rc<ExprNode>),
@@ -186,12 +202,21 @@ pub enum ExprNode {
     Not(Rc<ExprNode>),
     And(Rc<ExprNode>, Rc<ExprNode>),
     Or(Rc<ExprNode>, Rc<ExprNode>),
-    Call(String, Vec<Rc<ExprNode>>),
+    Cast(Rc<ExprNode>, Type),
+    Call(Symbol, Cell<Option<Address>>, Vec<Rc<ExprNode>>, usize),
 }
 
 impl ExprNode {
 
+    // variables and calls start out unresolved; the resolver fills in their frame address
+    pub fn var(name: Symbol) -> ExprNode {
+        ExprNode::Var(name, Cell::new(None))
+    }
 
+    // `line` is the call site, reported in runtime stack traces
+    pub fn call(name: Symbol, arguments: Vec<Rc<ExprNode>>, line: usize) -> ExprNode {
+        ExprNode::Call(name, Cell::new(None), arguments, line)
+    }
 }
 
 
@@ -1,5 +1,10 @@
-use core::panic;
+use std::fmt;
 use std::rc::Rc;
+use crate::bigint::BigInt;
+use crate::error::RuntimeError;
+use crate::native::Native;
+use crate::lexer::Lexer;
+use crate::token::Token;
 use crate::tree::{FuncNode};
 
 #[allow(dead_code)]
@@ -8,22 +13,370 @@ pub enum Value {
     Nil,
     Bool(bool),
     I32(i32),
+    I64(i64),
+    U8(u8),
+    U32(u32),
+    U64(u64),
+    BigInt(BigInt),
     F32(f32),
-    Chars(String),
+    F64(f64),
+    Char(char),
+    Str(String),
+    List(Rc<Vec<Value>>),
     Func(Rc<FuncNode>, usize),
+    Native(Rc<Native>),
+}
+
+/// A type that can be declared on a parameter or `let`, or named in an `as` cast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Type {
+    I32,
+    I64,
+    U8,
+    U32,
+    U64,
+    F32,
+    F64,
+    Char,
+    Bool,
+    Str,
+}
+
+impl Type {
+
+    pub fn from_token(token: &Token) -> Option<Type> {
+        match token {
+            Token::TYPE_I32 => Some(Type::I32),
+            Token::TYPE_I64 => Some(Type::I64),
+            Token::TYPE_U8 => Some(Type::U8),
+            Token::TYPE_U32 => Some(Type::U32),
+            Token::TYPE_U64 => Some(Type::U64),
+            Token::TYPE_F32 => Some(Type::F32),
+            Token::TYPE_F64 => Some(Type::F64),
+            Token::TYPE_CHAR => Some(Type::Char),
+            Token::TYPE_BOOL => Some(Type::Bool),
+            Token::TYPE_STRING => Some(Type::Str),
+            _ => None,
+        }
+    }
+
+    pub fn is_float(self) -> bool {
+        matches!(self, Type::F32 | Type::F64)
+    }
+
+    /// Smallest and largest value of an integer type.
+    pub fn range(self) -> Option<(i128, i128)> {
+        match self {
+            Type::I32 => Some((i32::MIN as i128, i32::MAX as i128)),
+            Type::I64 => Some((i64::MIN as i128, i64::MAX as i128)),
+            Type::U8 => Some((0, u8::MAX as i128)),
+            Type::U32 => Some((0, u32::MAX as i128)),
+            Type::U64 => Some((0, u64::MAX as i128)),
+            Type::F32 | Type::F64 | Type::Char | Type::Bool | Type::Str => None,
+        }
+    }
+
+    /// Type both operands of a mixed-width operation are brought to.
+    /// A float on either side gives a float (`f64` if either side is `f64`).
+    /// Two integers give the narrowest of `u8`, `u32`, `i32`, `u64`, `i64` that holds
+    /// every value of both; `u64` mixed with a signed type falls back to `i64`.
+    pub fn common(a: Type, b: Type) -> Type {
+        if a.is_float() || b.is_float() {
+            return if a == Type::F64 || b == Type::F64 { Type::F64 } else { Type::F32 };
+        }
+        let (Some((min_a, max_a)), Some((min_b, max_b))) = (a.range(), b.range()) else {
+            return a;
+        };
+        [Type::U8, Type::U32, Type::I32, Type::U64, Type::I64].into_iter()
+            .find(|kind| {
+                let (min, max) = kind.range().unwrap();
+                min <= min_a.min(min_b) && max_a.max(max_b) <= max
+            })
+            .unwrap_or(Type::I64)
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Type::I32 => "i32",
+            Type::I64 => "i64",
+            Type::U8 => "u8",
+            Type::U32 => "u32",
+            Type::U64 => "u64",
+            Type::F32 => "f32",
+            Type::F64 => "f64",
+            Type::Char => "char",
+            Type::Bool => "bool",
+            Type::Str => "string",
+        };
+        f.write_str(name)
+    }
 }
 
 impl Value {
-    pub fn print(&self) {
-        println!("{self:?}");
+
+    /// The value of a literal token.
+    pub fn from_token(token: &Token) -> Option<Value> {
+        match token {
+            Token::LIT_I32(n) => Some(Value::I32(*n)),
+            Token::LIT_I64(n) => Some(Value::I64(*n)),
+            Token::LIT_U8(n) => Some(Value::U8(*n)),
+            Token::LIT_U32(n) => Some(Value::U32(*n)),
+            Token::LIT_U64(n) => Some(Value::U64(*n)),
+            Token::LIT_BIGINT(digits) => BigInt::parse(digits).map(Value::BigInt),
+            Token::LIT_F32(x) => Some(Value::F32(*x)),
+            Token::LIT_F64(x) => Some(Value::F64(*x)),
+            Token::LIT_CHAR(c) => Some(Value::Char(*c)),
+            Token::LIT_STRING(text) => Some(Value::Str(text.clone())),
+            Token::LIT_BOOL(b) => Some(Value::Bool(*b)),
+            _ => None,
+        }
+    }
+
+    /// The value of `text` read as a single literal of the language (`5u8`, `2.5f64`,
+    /// `'a'`, `"two words"`, `true`), where a signed number may also start with `-`.
+    /// `None` when `text` is anything else.
+    pub fn parse_literal(text: &str) -> Option<Value> {
+        let (negative, literal) = match text.strip_prefix('-') {
+            Some(rest) => { (true, rest) }
+            None => { (false, text) }
+        };
+        // arguments that are not literals must not be interned as identifiers
+        let mut lexer = Lexer::literals(literal).ok()?;
+        let token = lexer.current();
+        lexer.advance().ok()?;
+        let next = lexer.current();
+        if next != Token::EOI {
+            return None;
+        }
+        let value = Value::from_token(&token)?;
+        if !negative {
+            return Some(value);
+        }
+        match value {
+            // -2147483648 is written as the negation of a BigInt literal
+            Value::I32(n) => { Some(Value::from(-&BigInt::from(n as i128))) }
+            Value::BigInt(n) => { Some(Value::from(-&n)) }
+            Value::I64(n) => { n.checked_neg().map(Value::I64) }
+            Value::F32(x) => { Some(Value::F32(-x)) }
+            Value::F64(x) => { Some(Value::F64(-x)) }
+            _ => { None }
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Nil => "Nil",
+            Value::Bool(_) => "Bool",
+            Value::I32(_) => "I32",
+            Value::I64(_) => "I64",
+            Value::U8(_) => "U8",
+            Value::U32(_) => "U32",
+            Value::U64(_) => "U64",
+            Value::BigInt(_) => "BigInt",
+            Value::F32(_) => "F32",
+            Value::F64(_) => "F64",
+            Value::Char(_) => "Char",
+            Value::Str(_) => "Str",
+            Value::List(_) => "List",
+            Value::Func(_, _) | Value::Native(_) => "Func",
+        }
+    }
+
+    /// Fixed-width number type of the value, if it has one.
+    pub fn kind(&self) -> Option<Type> {
+        match self {
+            Value::I32(_) => Some(Type::I32),
+            Value::I64(_) => Some(Type::I64),
+            Value::U8(_) => Some(Type::U8),
+            Value::U32(_) => Some(Type::U32),
+            Value::U64(_) => Some(Type::U64),
+            Value::F32(_) => Some(Type::F32),
+            Value::F64(_) => Some(Type::F64),
+            _ => None,
+        }
     }
 
-    pub fn unwrap(&self) -> bool{
+    /// Any integer value that fits in an `i128`.
+    pub fn to_i128(&self) -> Option<i128> {
         match self {
-            Value::Bool(b) => {
-                return *b;
-            },
-            _ => {panic!()}
+            Value::I32(n) => Some(*n as i128),
+            Value::I64(n) => Some(*n as i128),
+            Value::U8(n) => Some(*n as i128),
+            Value::U32(n) => Some(*n as i128),
+            Value::U64(n) => Some(*n as i128),
+            Value::BigInt(n) => n.to_i128(),
+            _ => None,
         }
     }
-}
\ No newline at end of file
+
+    /// Any number, integer or float, as an `f64`.
+    pub fn to_f64(&self) -> Option<f64> {
+        match self {
+            Value::F32(n) => Some(*n as f64),
+            Value::F64(n) => Some(*n),
+            Value::BigInt(n) => Some(n.to_f64()),
+            _ => self.to_i128().map(|n| n as f64),
+        }
+    }
+
+    /// Integer value of the given integer type, or `None` if it does not fit.
+    pub fn from_i128(n: i128, kind: Type) -> Option<Value> {
+        let value = match kind {
+            Type::I32 => Value::I32(i32::try_from(n).ok()?),
+            Type::I64 => Value::I64(i64::try_from(n).ok()?),
+            Type::U8 => Value::U8(u8::try_from(n).ok()?),
+            Type::U32 => Value::U32(u32::try_from(n).ok()?),
+            Type::U64 => Value::U64(u64::try_from(n).ok()?),
+            Type::F32 | Type::F64 | Type::Char | Type::Bool | Type::Str => { return None; }
+        };
+        Some(value)
+    }
+
+    /// Converts a value to a declared type without losing information:
+    /// integers must be in range and floats never become integers.
+    pub fn convert(self, kind: Type) -> Result<Value, RuntimeError> {
+        let converted = match kind {
+            Type::F32 => { self.to_f64().map(|n| Value::F32(n as f32)) }
+            Type::F64 => { self.to_f64().map(Value::F64) }
+            Type::Char => {
+                match &self {
+                    Value::Char(_) => { Some(self.clone()) }
+                    _ => { None }
+                }
+            }
+            Type::Bool => {
+                match &self {
+                    Value::Bool(_) => { Some(self.clone()) }
+                    _ => { None }
+                }
+            }
+            Type::Str => {
+                match &self {
+                    Value::Str(_) => { Some(self.clone()) }
+                    _ => { None }
+                }
+            }
+            _ => { self.to_i128().and_then(|n| Value::from_i128(n, kind)) }
+        };
+        converted.ok_or_else(|| RuntimeError::InvalidConversion { value: self.described(), target: kind })
+    }
+
+    // the value in a conversion error: numbers with their type, text in quotes
+    fn described(&self) -> String {
+        match self {
+            Value::Str(text) => { format!("\"{text}\"") }
+            Value::Char(c) => { format!("'{c}'") }
+            _ => {
+                match self.kind() {
+                    Some(kind) => { format!("{kind} {self}") }
+                    None => { self.to_string() }
+                }
+            }
+        }
+    }
+
+    /// Explicit `as` conversion. Unlike `convert` it may lose information:
+    ///
+    /// | from \ to | integer             | float      | char          | bool           | string         |
+    /// |-----------|---------------------|------------|---------------|----------------|----------------|
+    /// | integer   | must be in range    | nearest    | code point    | `n != 0`       | digits         |
+    /// | float     | truncated, must fit | nearest    | -             | `x != 0.0`     | digits         |
+    /// | char      | code point          | -          | itself        | -              | one character  |
+    /// | bool      | 0 or 1              | 0.0 or 1.0 | -             | itself         | `true`/`false` |
+    /// | string    | parsed              | parsed     | one character | `true`/`false` | itself         |
+    ///
+    /// Every other combination, and every parse or range failure, is a runtime error.
+    pub fn cast(self, kind: Type) -> Result<Value, RuntimeError> {
+        let cast = match (&self, kind) {
+            (Value::Nil | Value::List(_) | Value::Func(_, _) | Value::Native(_), _) => { None }
+            (Value::Bool(b), Type::Bool) => { Some(Value::Bool(*b)) }
+            (Value::Bool(b), Type::Str) => { Some(Value::Str(b.to_string())) }
+            (Value::Bool(b), _) if kind != Type::Char => { Value::I32(*b as i32).cast(kind).ok() }
+            (Value::Char(c), Type::Char) => { Some(Value::Char(*c)) }
+            (Value::Char(c), Type::Str) => { Some(Value::Str(c.to_string())) }
+            (Value::Char(c), _) if kind.range().is_some() => { Value::from_i128(*c as i128, kind) }
+            (Value::Char(_), _) => { None }
+            (Value::Str(text), Type::Str) => { Some(Value::Str(text.clone())) }
+            (Value::Str(text), Type::Char) => {
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => { Some(Value::Char(c)) }
+                    _ => { None }
+                }
+            }
+            (Value::Str(text), Type::Bool) => { text.trim().parse().ok().map(Value::Bool) }
+            (Value::Str(text), Type::F32 | Type::F64) => {
+                text.trim().parse::<f64>().ok().and_then(|n| Value::F64(n).convert(kind).ok())
+            }
+            (Value::Str(text), _) => {
+                BigInt::parse(text.trim()).and_then(|n| Value::BigInt(n).convert(kind).ok())
+            }
+            (_, Type::Str) => {
+                match &self {
+                    Value::F32(n) => { Some(Value::Str(n.to_string())) }
+                    Value::F64(n) => { Some(Value::Str(n.to_string())) }
+                    Value::BigInt(n) => { Some(Value::Str(n.to_string())) }
+                    _ => { self.to_i128().map(|n| Value::Str(n.to_string())) }
+                }
+            }
+            (_, Type::Bool) => { self.to_f64().map(|n| Value::Bool(n != 0.0)) }
+            (_, Type::Char) => {
+                self.to_i128()
+                    .and_then(|n| u32::try_from(n).ok())
+                    .and_then(char::from_u32)
+                    .map(Value::Char)
+            }
+            (_, Type::F32 | Type::F64) => { self.clone().convert(kind).ok() }
+            (Value::F32(_) | Value::F64(_), _) => {
+                let n = self.to_f64().unwrap().trunc();
+                // NaN and infinities fail here, as does anything past the i128 range
+                if n.is_finite() && n.abs() < 1e38 {
+                    Value::from_i128(n as i128, kind)
+                } else {
+                    None
+                }
+            }
+            (_, _) => { self.clone().convert(kind).ok() }
+        };
+        cast.ok_or_else(|| RuntimeError::InvalidConversion { value: self.described(), target: kind })
+    }
+}
+
+// what `print` shows: no variant names and no quotes
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Nil => { write!(f, "nil") }
+            Value::Bool(b) => { write!(f, "{b}") }
+            Value::I32(n) => { write!(f, "{n}") }
+            Value::I64(n) => { write!(f, "{n}") }
+            Value::U8(n) => { write!(f, "{n}") }
+            Value::U32(n) => { write!(f, "{n}") }
+            Value::U64(n) => { write!(f, "{n}") }
+            Value::BigInt(n) => { write!(f, "{n}") }
+            // debug formatting keeps the `.0` on whole floats
+            Value::F32(n) => { write!(f, "{n:?}") }
+            Value::F64(n) => { write!(f, "{n:?}") }
+            Value::Char(c) => { write!(f, "{c}") }
+            Value::Str(text) => { write!(f, "{text}") }
+            Value::List(items) => {
+                let texts: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+                write!(f, "[{}]", texts.join(", "))
+            }
+            Value::Func(func, _) => { write!(f, "<func {}>", func.name) }
+            Value::Native(native) => { write!(f, "<native {}>", native.name) }
+        }
+    }
+}
+
+// integer results go back to I32 whenever they fit
+impl From<BigInt> for Value {
+    fn from(number: BigInt) -> Value {
+        match number.to_i32() {
+            Some(small) => Value::I32(small),
+            None => Value::BigInt(number),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use common::{printed_with, run_with};
+
+#[test]
+fn a_single_parameter_gets_every_argument() {
+    let lines = printed_with("
+func main(arguments)
+[
+print len(arguments), arguments;
+]
+", &["one", "-x", "3"]);
+    assert_eq!(lines, ["3 [one, -x, 3]"]);
+}
+
+#[test]
+fn typed_parameters_are_parsed() {
+    let lines = printed_with("
+func main(name, count: i32, scale: f64)
+[
+print name, count + 1, scale * 2.0;
+]
+", &["abc", "41", "1.5"]);
+    assert_eq!(lines, ["abc 42 3.0"]);
+}
+
+#[test]
+fn args_returns_the_arguments_anywhere() {
+    let lines = printed_with("
+func first()
+[
+return get(args(), 0);
+]
+func main()
+[
+print first(), len(args());
+]
+", &["hello", "world"]);
+    assert_eq!(lines, ["hello 2"]);
+}
+
+#[test]
+fn flags_before_the_arguments_still_apply() {
+    let output = run_with("
+func main(n: i32)
+[
+print n * 2147483647;
+]
+", &["--overflow", "trap", "2"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("overflow"));
+}
+
+#[test]
+fn wrong_argument_counts_and_types_are_runtime_errors() {
+    let source = "
+func main(a: i32, b: i32)
+[
+print a + b;
+]
+";
+    let output = run_with(source, &["1"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Function 'main' expects 2 arguments but got 1"));
+
+    let output = run_with(source, &["1", "x"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Cannot convert \"x\" to i32"));
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::{printed, printed_with, run_with};
+
+const FACTORIAL_13: &str = "
+func fact(n)
+[
+if n < 2 [
+return 1;
+]
+return n * fact(n - 1);
+]
+func main()
+[
+print fact(13);
+]
+";
+
+const FACTORIAL_50: &str = "
+func fact(n)
+[
+if n < 2 [
+return 1;
+]
+return n * fact(n - 1);
+]
+func main()
+[
+print fact(50);
+print fact(50) / fact(48);
+]
+";
+
+#[test]
+fn overflow_promotes_by_default() {
+    assert_eq!(printed(FACTORIAL_50), [
+        "30414093201713378043612608166064768844377641568960512000000000000",
+        "2450",
+    ]);
+}
+
+#[test]
+fn big_literals_mix_with_small_integers() {
+    assert_eq!(printed("
+func main()
+[
+print 99999999999999999999 + 1;
+print 0 - 2147483648 - 1;
+print 10000000000 > 7;
+print 3 == 3000000000 / 1000000000;
+print 1.5 * 4000000000;
+]
+"), [
+        "100000000000000000000",
+        "-2147483649",
+        "true",
+        "true",
+        "6000000000.0",
+    ]);
+}
+
+#[test]
+fn overflow_traps_when_asked() {
+    let output = run_with(FACTORIAL_13, &["--overflow", "trap"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Integer overflow in 'Mul' of 13 and 479001600"), "{stderr}");
+}
+
+#[test]
+fn overflow_wraps_when_asked() {
+    assert_eq!(printed_with(FACTORIAL_13, &["--overflow", "wrap"]), ["1932053504"]);
+}
+
+#[test]
+fn overflow_saturates_when_asked() {
+    assert_eq!(printed_with(FACTORIAL_13, &["--overflow", "saturate"]), ["2147483647"]);
+}
+
+#[test]
+fn division_by_zero_is_a_runtime_error() {
+    for mode in ["promote", "trap", "wrap", "saturate"] {
+        let output = run_with("
+func main()
+[
+let zero;
+zero = 0;
+print 7 / zero;
+]
+", &["--overflow", mode]);
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Division by zero"));
+    }
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::{printed, run};
+
+#[test]
+fn numbers_cast_between_widths() {
+    assert_eq!(printed("
+func main()
+[
+print 7 as f32;
+print 2.9 as i32;
+print 0 - 2.9 as i32;
+print 65 as u8;
+print 300 as i64 + 1;
+print 1 + 2 as f64;
+]
+"), ["7.0", "2", "-2", "65", "301", "3.0"]);
+}
+
+#[test]
+fn strings_chars_and_bools_cast() {
+    assert_eq!(printed("
+func main()
+[
+print \"42\" as i32 + 1;
+print \"2.5\" as f32;
+print 65 as char;
+print 'A' as string;
+print 12 as string;
+print true as i32;
+print 0 as bool;
+print \"true\" as bool;
+]
+"), ["43", "2.5", "A", "A", "12", "1", "false", "true"]);
+}
+
+#[test]
+fn failed_casts_are_runtime_errors() {
+    for (cast, message) in [
+        ("\"abc\" as i32", "Cannot convert \"abc\" to i32"),
+        ("300 as u8", "Cannot convert i32 300 to u8"),
+        ("2.5 as char", "Cannot convert f32 2.5 to char"),
+        ("\"maybe\" as bool", "Cannot convert \"maybe\" to bool"),
+    ] {
+        let output = run(&format!("
+func main()
+[
+print {cast};
+]
+"));
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(message), "{stderr}");
+    }
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use common::{printed, printed_with, run, stderr_of};
+
+fn program(expr: &str) -> String {
+    format!("
+func main()
+[
+print {expr};
+]
+")
+}
+
+#[test]
+fn strict_mode_rejects_strings_with_numbers() {
+    assert!(stderr_of(&program("\"1\" + 2"), &[]).contains("Cannot perform 'Add' on Str and I32"));
+    assert!(stderr_of(&program("2 * \"3\""), &[]).contains("Cannot perform 'Mul' on I32 and Str"));
+    assert!(stderr_of(&program("\"10\" > 9"), &[]).contains("Cannot perform 'GreaterThan' on Str and I32"));
+}
+
+#[test]
+fn lenient_mode_parses_strings_as_numbers() {
+    let lenient = ["--coercion", "lenient"];
+    assert_eq!(printed_with(&program("\"1\" + 2"), &lenient), ["3"]);
+    assert_eq!(printed_with(&program("\"2.5\" * 2"), &lenient), ["5.0"]);
+    assert_eq!(printed_with(&program("\"10\" > 9"), &lenient), ["true"]);
+    assert!(stderr_of(&program("\"abc\" + 1"), &lenient).contains("Cannot convert \"abc\" to number"));
+}
+
+#[test]
+fn strings_concatenate_in_both_modes() {
+    assert_eq!(printed(&program("\"ab\" + \"cd\"")), ["abcd"]);
+    assert_eq!(printed_with(&program("\"ab\" + \"cd\""), &["--coercion", "lenient"]), ["abcd"]);
+}
+
+#[test]
+fn booleans_only_compare_for_equality() {
+    assert_eq!(printed(&program("true == false")), ["false"]);
+    assert!(!run(&program("true < false")).status.success());
+    assert!(stderr_of(&program("true + 1"), &["--coercion", "lenient"]).contains("Left operand of 'Add' is Bool"));
+}
@@ -0,0 +1,89 @@
+// each test crate uses a different subset of these helpers
+#![allow(dead_code)]
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use PL_Final::{Error, Interpreter, Machine, Runtime};
+
+static NEXT_PROGRAM: AtomicUsize = AtomicUsize::new(0);
+
+/// Writes `source` to a scratch file and runs it through the interpreter binary.
+pub fn run(source: &str) -> Output {
+    run_with(source, &[])
+}
+
+/// Like `run`, with extra command line flags.
+pub fn run_with(source: &str, flags: &[&str]) -> Output {
+    run_with_input(source, flags, "")
+}
+
+/// Like `run_with`, feeding `input` to the program's stdin.
+pub fn run_with_input(source: &str, flags: &[&str], input: &str) -> Output {
+    launch(source, &["-r"], flags, input)
+}
+
+/// Runs the interpreter binary as `PL_Final <before> <scratch file> <after>`.
+pub fn launch(source: &str, before: &[&str], after: &[&str], input: &str) -> Output {
+    let id = NEXT_PROGRAM.fetch_add(1, Ordering::Relaxed);
+    let path: PathBuf = std::env::temp_dir().join(format!("tbd_test_{}_{id}.tbd", std::process::id()));
+    fs::write(&path, source).expect("Failed to write test program.");
+
+    let path_argument = path.to_str().expect("Temporary path is not UTF-8.");
+    let arguments: Vec<&str> = before.iter().copied().chain([path_argument]).chain(after.iter().copied()).collect();
+    let output = interpreter(&arguments, input);
+
+    fs::remove_file(&path).ok();
+    output
+}
+
+/// Runs the interpreter binary with exactly these arguments, feeding `input` to its stdin.
+pub fn interpreter(arguments: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_PL_Final"))
+        .args(arguments)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to launch interpreter.");
+    // dropping stdin after the write closes it, so the program sees the end of the input
+    child.stdin.take().unwrap().write_all(input.as_bytes()).ok();
+    child.wait_with_output().expect("Failed to wait for interpreter.")
+}
+
+/// Everything a successful run printed; the interpreter's own trace goes to stderr.
+pub fn stdout_of(output: &Output) -> String {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// Lines the program printed.
+pub fn printed(source: &str) -> Vec<String> {
+    printed_with(source, &[])
+}
+
+/// Like `printed`, with extra command line flags.
+pub fn printed_with(source: &str, flags: &[&str]) -> Vec<String> {
+    stdout_of(&run_with(source, flags))
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+/// What a run that must fail reported on stderr.
+pub fn stderr_of(source: &str, flags: &[&str]) -> String {
+    let output = run_with(source, flags);
+    assert!(!output.status.success());
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+/// Loads a program in-process, with error reports discarded.
+pub fn load(runtime: Runtime, source: &str) -> Result<Machine, Error> {
+    let mut runtime = runtime;
+    runtime.set_diagnostics(Box::new(io::sink()));
+    Interpreter::with_runtime(runtime).load_source(source)
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use common::printed;
+
+#[test]
+fn break_leaves_the_innermost_loop() {
+    let output = printed("
+func main()
+[
+let i;
+i = 0;
+while true [
+i = i + 1;
+if i == 3 [
+break;
+]
+print i;
+]
+print 100;
+]
+");
+    assert_eq!(output, ["1", "2", "100"]);
+}
+
+#[test]
+fn continue_skips_the_rest_of_the_body() {
+    let output = printed("
+func main()
+[
+let i;
+i = 0;
+while i < 5 [
+i = i + 1;
+if i == 2 [
+continue;
+] else [
+if i == 4 [
+continue;
+]
+]
+print i;
+]
+]
+");
+    assert_eq!(output, ["1", "3", "5"]);
+}
+
+#[test]
+fn return_inside_loop_leaves_the_function() {
+    let output = printed("
+func find(limit)
+[
+let i;
+i = 0;
+while i < limit [
+if i * i > 10 [
+return i;
+]
+i = i + 1;
+]
+return 0 - 1;
+]
+func main()
+[
+print find(100);
+print find(2);
+]
+");
+    assert_eq!(output, ["4", "-1"]);
+}
+
+#[test]
+fn break_in_nested_loop_keeps_outer_loop_running() {
+    let output = printed("
+func main()
+[
+let i;
+let j;
+i = 0;
+while i < 3 [
+i = i + 1;
+j = 0;
+while true [
+j = j + 1;
+if j == i [
+break;
+]
+]
+print j;
+]
+]
+");
+    assert_eq!(output, ["1", "2", "3"]);
+}
+
+#[test]
+fn loop_that_never_runs_falls_through() {
+    let output = printed("
+func f()
+[
+while false [
+return 1;
+]
+return 2;
+]
+func main()
+[
+print f();
+]
+");
+    assert_eq!(output, ["2"]);
+}
+
+#[test]
+fn calls_nest_inside_call_arguments() {
+    assert_eq!(printed("
+func add(a, b)
+[
+return a + b;
+]
+func main()
+[
+print add(add(1, 2), (3 + 4) * 2), add(1, add(2, 3));
+]
+"), ["17 6"]);
+}
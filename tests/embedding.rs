@@ -0,0 +1,134 @@
+mod common;
+
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use common::load;
+use PL_Final::{Error, Interpreter, Machine, OverflowMode, ParseError, Runtime, Value};
+
+/// In-memory sink the test keeps a handle to after giving one to the runtime.
+#[derive(Clone, Default)]
+struct Buffer(Rc<RefCell<Vec<u8>>>);
+
+impl Buffer {
+    fn text(&self) -> String {
+        String::from_utf8(self.0.borrow().clone()).unwrap()
+    }
+}
+
+impl Write for Buffer {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn capture(source: &str) -> (Result<Machine, Error>, Buffer, Buffer) {
+    let (output, diagnostics) = (Buffer::default(), Buffer::default());
+    let mut runtime = Runtime::new();
+    runtime.set_output(Box::new(output.clone()));
+    runtime.set_diagnostics(Box::new(diagnostics.clone()));
+    (Interpreter::with_runtime(runtime).load_source(source), output, diagnostics)
+}
+
+const FACTORIAL: &str = "
+func factorial(n)
+[
+if n < 2
+[
+return 1;
+]
+return n * factorial(n - 1);
+]
+func main()
+[
+print \"main ran\";
+]
+";
+
+#[test]
+fn call_returns_the_function_result() {
+    let machine = Interpreter::new().load_source(FACTORIAL).unwrap();
+    assert_eq!(machine.call("factorial", vec![Value::I32(10)]).unwrap().to_string(), "3628800");
+}
+
+#[test]
+fn print_output_can_be_captured() {
+    let (machine, output, diagnostics) = capture(FACTORIAL);
+    machine.unwrap().run().unwrap();
+    assert_eq!(output.text(), "main ran\n");
+    assert_eq!(diagnostics.text(), "");
+}
+
+#[test]
+fn runtime_settings_apply() {
+    let mut runtime = Runtime::new();
+    runtime.overflow = OverflowMode::Trap;
+    let machine = load(runtime, FACTORIAL).unwrap();
+    let error = machine.call("factorial", vec![Value::I32(13)]).unwrap_err();
+    assert!(matches!(error, Error::Runtime(_)), "{error}");
+    assert_eq!(error.stack_trace().len(), 1);
+}
+
+#[test]
+fn errors_are_returned_and_reported() {
+    let (machine, _, diagnostics) = capture(FACTORIAL);
+    let error = machine.unwrap().call("missing", vec![]).unwrap_err();
+    assert_eq!(error.to_string(), "Runtime error: Cannot find function 'missing'");
+    assert_eq!(diagnostics.text(), "Runtime error: Cannot find function 'missing'\n");
+
+    let (machine, _, diagnostics) = capture("func main() [ print x; ]");
+    assert!(matches!(machine, Err(Error::Analysis(_))));
+    assert_eq!(diagnostics.text(), "Analysis error: Undeclared identifier 'x' in function main\n");
+
+    let (machine, _, _) = capture("func main( [ ]");
+    assert!(matches!(machine, Err(Error::Parse(_))));
+}
+
+#[test]
+fn parse_errors_say_where_they_are() {
+    let (machine, _, diagnostics) = capture("
+func main()
+[
+print (1 + ;
+]
+");
+    assert!(matches!(machine, Err(Error::Parse(ParseError::Unexpected { line: 4, .. }))));
+    assert_eq!(diagnostics.text(), "Parse error: Expected an expression but found end of input on line 4\n");
+}
+
+#[test]
+fn errors_can_be_formatted_on_another_thread() {
+    let (machine, _, _) = capture("
+func main()
+[
+print zzz_undefined_name;
+]
+");
+    let error = machine.err().unwrap();
+    let message = std::thread::spawn(move || error.to_string()).join().unwrap();
+    assert_eq!(message, "Analysis error: Undeclared identifier 'zzz_undefined_name' in function main");
+}
+
+#[test]
+fn programs_can_be_loaded_on_many_threads_at_once() {
+    let threads: Vec<_> = (0..8)
+        .map(|n| {
+            std::thread::spawn(move || {
+                if n % 2 == 0 {
+                    matches!(load(Runtime::new(), "func main("), Err(Error::Parse(_)))
+                } else {
+                    load(Runtime::new(), FACTORIAL).is_ok()
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        assert!(thread.join().unwrap());
+    }
+}
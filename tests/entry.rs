@@ -0,0 +1,88 @@
+mod common;
+
+use std::process::Output;
+
+use common::{launch, run_with, stdout_of};
+use PL_Final::Value;
+
+const LIBRARY: &str = "
+func factorial_loop(n)
+[
+let result;
+result = 1;
+while n > 1
+[
+result = result * n;
+n = n - 1;
+]
+return result;
+]
+func describe(name, count: i64, ready)
+[
+print name, count, ready;
+return count * 2;
+]
+func kinds(small, letter, wide, negative, word)
+[
+print small + 1u8, letter == 'a', wide / 4.0f64, negative - 1, len(word);
+return small + 1u8;
+]
+func main()
+[
+print \"main\";
+]
+";
+
+// no -r: --call is an action of its own
+fn call(flags: &[&str]) -> Output {
+    launch(LIBRARY, &[], flags, "")
+}
+
+#[test]
+fn call_prints_the_result() {
+    assert_eq!(stdout_of(&call(&["--call", "factorial_loop", "10"])), "3628800\n");
+    assert_eq!(stdout_of(&call(&["--call", "factorial_loop", "30"])), "265252859812191058636308480000000\n");
+}
+
+#[test]
+fn call_arguments_are_literals() {
+    let output = call(&["--call", "describe", "\"two words\"", "21", "true"]);
+    assert_eq!(stdout_of(&output), "two words 21 true\n42\n");
+}
+
+#[test]
+fn call_arguments_keep_their_suffix_and_char_types() {
+    let output = call(&["--call", "kinds", "254u8", "'a'", "1.0f64", "-5i64", "nan"]);
+    assert_eq!(stdout_of(&output), "255 true 0.25 -6 3\n255\n");
+
+    // still a u8, so adding one more overflows
+    let output = call(&["--call", "kinds", "255u8", "'a'", "1.0f64", "-5i64", "nan"]);
+    assert_eq!(output.status.code(), Some(70));
+}
+
+#[test]
+fn only_whole_literals_are_read_as_values() {
+    assert!(matches!(Value::parse_literal("-5i64"), Some(Value::I64(-5))));
+    assert!(matches!(Value::parse_literal("'a'"), Some(Value::Char('a'))));
+    assert!(Value::parse_literal("nan").is_none());
+    assert!(Value::parse_literal("two words").is_none());
+    assert!(Value::parse_literal("5 +").is_none());
+    assert!(Value::parse_literal("\"open").is_none());
+}
+
+#[test]
+fn call_errors_exit_like_runs() {
+    let output = call(&["--call", "missing"]);
+    assert_eq!(output.status.code(), Some(70));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Cannot find function 'missing'"));
+
+    let output = call(&["--call", "factorial_loop"]);
+    assert_eq!(output.status.code(), Some(70));
+}
+
+#[test]
+fn entry_replaces_main() {
+    let output = run_with(LIBRARY, &["--entry", "describe", "x", "4", "no"]);
+    assert_eq!(output.status.code(), Some(8));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "x 4 no\n");
+}
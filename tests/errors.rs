@@ -0,0 +1,86 @@
+mod common;
+
+use common::stderr_of;
+
+#[test]
+fn nested_call_errors_show_the_whole_call_stack() {
+    assert_eq!(stderr_of("
+func inner(n)
+[
+return n / 0;
+]
+func outer(n)
+[
+let x;
+x = inner(n);
+return x;
+]
+func main()
+[
+print outer(3);
+]
+", &[]), "\
+Runtime error: Division by zero
+    in inner (called from line 9)
+    in outer (called from line 14)
+    in main
+");
+}
+
+#[test]
+fn a_missing_main_is_a_runtime_error() {
+    assert_eq!(stderr_of("
+func helper()
+[
+]
+", &[]), "Runtime error: Cannot find function 'main'\n");
+}
+
+#[test]
+fn conditions_must_be_booleans() {
+    assert_eq!(stderr_of("
+func main()
+[
+if 1
+[
+print 2;
+]
+]
+", &[]), "Runtime error: If-then-else statement condition must be of type boolean\n    in main\n");
+
+    assert_eq!(stderr_of("
+func main()
+[
+let n;
+n = 1;
+while n
+[
+n = n - 1;
+]
+]
+", &[]), "Runtime error: While statement condition must be of type boolean\n    in main\n");
+}
+
+#[test]
+fn malformed_programs_are_parse_errors() {
+    assert_eq!(stderr_of("
+func main()
+[
+print ;
+]
+", &[]), "Parse error: Expected an expression but found ';' on line 4\n");
+
+    assert_eq!(stderr_of("
+func main()
+[
+print 300u8;
+]
+", &[]), "Parse error: Literal '300' does not fit in u8 on line 4\n");
+}
+
+#[test]
+fn unfinished_programs_are_parse_errors() {
+    assert_eq!(stderr_of("func main() [", &[]), "Parse error: Expected a statement but found end of input on line 1\n");
+    assert_eq!(stderr_of("func main() [ print \"a; ]", &[]), "Parse error: Unterminated string on line 1\n");
+    assert_eq!(stderr_of("func main() [ print (1; ]", &[]), "Parse error: Expected ')' but found end of input on line 1\n");
+}
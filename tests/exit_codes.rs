@@ -0,0 +1,95 @@
+mod common;
+
+use common::{run, run_with};
+
+#[test]
+fn an_integer_from_main_is_the_exit_code() {
+    let output = run_with("
+func main(code: i32)
+[
+print code;
+return code;
+]
+", &["3"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "3\n");
+
+    let output = run("
+func main()
+[
+return 0u8;
+]
+");
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn integers_that_are_not_exit_statuses_are_failures() {
+    for result in ["256", "0 - 1", "100000000000000000000", "255 + 1u8"] {
+        let output = run(&format!("
+func main()
+[
+return {result};
+]
+"));
+        assert_eq!(output.status.code(), Some(71), "{result}");
+    }
+    let output = run("
+func main()
+[
+return 255;
+]
+");
+    assert_eq!(output.status.code(), Some(255));
+}
+
+#[test]
+fn other_results_are_success() {
+    for result in ["", "return \"done\";", "return 2.5;", "return true;"] {
+        let output = run(&format!("
+func main()
+[
+{result}
+]
+"));
+        assert_eq!(output.status.code(), Some(0), "{result}");
+    }
+}
+
+#[test]
+fn runtime_errors_have_their_own_code() {
+    let output = run("
+func main()
+[
+print 1 / 0;
+]
+");
+    assert_eq!(output.status.code(), Some(70));
+}
+
+#[test]
+fn invalid_programs_have_their_own_code() {
+    let output = run("
+func main()
+[
+print missing;
+]
+");
+    assert_eq!(output.status.code(), Some(65));
+
+    let output = run("
+func main(
+");
+    assert_eq!(output.status.code(), Some(65));
+}
+
+#[test]
+fn an_unusable_allow_fs_directory_is_a_usage_error() {
+    let output = run_with("
+func main()
+[
+]
+", &["--allow-fs", "/nonexistent/tbd_sandbox"]);
+    assert_eq!(output.status.code(), Some(64));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Cannot allow file access to /nonexistent/tbd_sandbox"));
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use common::{run, run_with, stderr_of};
+
+// a fresh directory per test, with a sibling file the sandbox must not reach
+fn sandbox(name: &str) -> PathBuf {
+    let base = std::env::temp_dir().join(format!("tbd_files_{}_{name}", std::process::id()));
+    fs::remove_dir_all(&base).ok();
+    fs::create_dir_all(base.join("data")).unwrap();
+    fs::write(base.join("secret.txt"), "hidden").unwrap();
+    base
+}
+
+#[test]
+fn file_access_is_off_by_default() {
+    let output = run("
+func main()
+[
+print file_exists(\"anything.txt\");
+]
+");
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Access to 'anything.txt' denied: file access is off"));
+}
+
+#[test]
+fn files_round_trip_inside_the_directory() {
+    let base = sandbox("round_trip");
+    let dir = base.join("data");
+    let output = run_with("
+func main()
+[
+let newline;
+let done;
+let lines;
+let i;
+newline = to_string(10 as char);
+print file_exists(\"notes.txt\");
+done = write_file(\"notes.txt\", \"first\");
+done = append_file(\"notes.txt\", newline + \"second\" + newline);
+print file_exists(\"notes.txt\"), len(read_file(\"notes.txt\"));
+lines = read_lines(\"notes.txt\");
+i = 0;
+while i < len(lines)
+[
+print i, get(lines, i);
+i = i + 1;
+]
+]
+", &["--allow-fs", dir.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "false\ntrue 13\n0 first\n1 second\n");
+    assert_eq!(fs::read_to_string(dir.join("notes.txt")).unwrap(), "first\nsecond\n");
+    fs::remove_dir_all(base).ok();
+}
+
+#[test]
+fn paths_cannot_leave_the_directory() {
+    let base = sandbox("escape");
+    let dir = base.join("data");
+    let secret = base.join("secret.txt");
+    for path in ["../secret.txt", secret.to_str().unwrap(), "missing/../../secret.txt"] {
+        let stderr = stderr_of(&format!("func main()\n[\nprint read_file(\"{path}\");\n]\n"),
+            &["--allow-fs", dir.to_str().unwrap()]);
+        assert!(stderr.contains("denied"), "{path}: {stderr}");
+    }
+    fs::remove_dir_all(base).ok();
+}
+
+#[test]
+fn missing_files_are_runtime_errors() {
+    let base = sandbox("missing");
+    let dir = base.join("data");
+    let stderr = stderr_of("
+func main()
+[
+print read_file(\"absent.txt\");
+]
+", &["--allow-fs", dir.to_str().unwrap()]);
+    assert!(stderr.contains("Cannot access 'absent.txt'"), "{stderr}");
+    fs::remove_dir_all(base).ok();
+}
+
+#[cfg(unix)]
+#[test]
+fn dangling_links_cannot_create_files_outside() {
+    let base = sandbox("dangling");
+    let dir = base.join("data");
+    std::os::unix::fs::symlink(base.join("planted.txt"), dir.join("link.txt")).unwrap();
+    let stderr = stderr_of("
+func main()
+[
+let ignored;
+ignored = write_file(\"link.txt\", \"escaped\");
+]
+", &["--allow-fs", dir.to_str().unwrap()]);
+    assert!(stderr.contains("Access to 'link.txt' denied"), "{stderr}");
+    assert!(!base.join("planted.txt").exists());
+    fs::remove_dir_all(base).ok();
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::{run_with_input, stdout_of};
+use PL_Final::{Interpreter, Runtime};
+
+#[test]
+fn reads_lines_and_numbers() {
+    assert_eq!(stdout_of(&run_with_input("
+func main()
+[
+let name;
+print \"name?\";
+name = read_line();
+print \"hello \" + name;
+print read_int() + 1, read_float() * 2;
+]
+", &[], "Ada\r\n 41 \n1.25\n")), "name?\nhello Ada\n42 2.5\n");
+}
+
+#[test]
+fn end_of_input_ends_a_loop() {
+    assert_eq!(stdout_of(&run_with_input("
+func main()
+[
+let total;
+total = 0;
+while not end_of_input()
+[
+total = total + read_int();
+]
+print total;
+]
+", &[], "1\n2\n3")), "6\n");
+}
+
+#[test]
+fn reading_past_the_end_or_bad_numbers_are_errors() {
+    let source = "
+func main()
+[
+print read_int();
+]
+";
+    let output = run_with_input(source, &[], "");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Input ended before a line could be read"));
+    let output = run_with_input(source, &[], "forty\n");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Cannot convert \"forty\" to integer"));
+}
+
+#[test]
+fn hosts_can_supply_input() {
+    let mut runtime = Runtime::new();
+    runtime.set_input(Box::new(Cursor::new("7\n")));
+    let machine = Interpreter::with_runtime(runtime).load_source("
+func twice()
+[
+return read_int() * 2;
+]
+func main()
+[
+]
+").unwrap();
+    assert_eq!(machine.call("twice", vec![]).unwrap().to_string(), "14");
+}
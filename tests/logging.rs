@@ -0,0 +1,58 @@
+mod common;
+
+use common::run_with;
+
+const PROGRAM: &str = "
+func main()
+[
+let x;
+x = 1 + 2;
+print x;
+]
+";
+
+fn stderr(flags: &[&str]) -> String {
+    let output = run_with(PROGRAM, flags);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "3\n");
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn default_run_is_silent_apart_from_program_output() {
+    assert_eq!(stderr(&[]), "");
+}
+
+#[test]
+fn verbose_logs_phases() {
+    let log = stderr(&["-v"]);
+    assert!(log.contains("[info] Analyze."), "{log}");
+    assert!(!log.contains("[debug]"), "{log}");
+}
+
+#[test]
+fn very_verbose_logs_statements_and_parse_tree() {
+    let log = stderr(&["-vv"]);
+    assert!(log.contains("[debug] executing print statement"), "{log}");
+    assert!(!log.contains("[trace]"), "{log}");
+
+    let log = stderr(&["-vvv"]);
+    assert!(log.contains("[trace] KW_FUNC"), "{log}");
+}
+
+#[test]
+fn quiet_still_reports_runtime_errors() {
+    let output = run_with("
+func main()
+[
+print 1 / 0;
+]
+", &["-q"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Division by zero"));
+}
+
+#[test]
+fn quiet_and_verbose_conflict() {
+    assert!(!run_with(PROGRAM, &["-q", "-v"]).status.success());
+}
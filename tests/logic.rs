@@ -0,0 +1,80 @@
+mod common;
+
+use common::{printed, run};
+
+// `yes` and `no` print a marker every time they are called
+const HELPERS: &str = "
+func yes()
+[
+print 1;
+return true;
+]
+func no()
+[
+print 0;
+return false;
+]
+";
+
+fn with_helpers(main: &str) -> String {
+    format!("{HELPERS}\nfunc main()\n[\n{main}\n]\n")
+}
+
+#[test]
+fn and_skips_right_operand_when_left_is_false() {
+    let output = printed(&with_helpers("print no() and yes();"));
+    assert_eq!(output, ["0", "false"]);
+}
+
+#[test]
+fn and_evaluates_right_operand_when_left_is_true() {
+    let output = printed(&with_helpers("print yes() and no();"));
+    assert_eq!(output, ["1", "0", "false"]);
+}
+
+#[test]
+fn or_skips_right_operand_when_left_is_true() {
+    let output = printed(&with_helpers("print yes() or no();"));
+    assert_eq!(output, ["1", "true"]);
+}
+
+#[test]
+fn or_evaluates_right_operand_when_left_is_false() {
+    let output = printed(&with_helpers("print no() or yes();"));
+    assert_eq!(output, ["0", "1", "true"]);
+}
+
+#[test]
+fn not_negates() {
+    let output = printed(&with_helpers("print not true;\nprint not false;\nprint not no();"));
+    assert_eq!(output, ["false", "true", "0", "true"]);
+}
+
+#[test]
+fn guard_protects_division() {
+    let output = printed("
+func check(n)
+[
+return n != 0 and 10 / n > 1;
+]
+func main()
+[
+print check(0);
+print check(2);
+print check(20);
+]
+");
+    assert_eq!(output, ["false", "true", "false"]);
+}
+
+#[test]
+fn non_boolean_operand_is_an_error() {
+    let output = run("
+func main()
+[
+print 1 and true;
+]
+");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Operands of 'And' must be of type boolean, found I32"));
+}
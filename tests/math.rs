@@ -0,0 +1,90 @@
+mod common;
+
+use common::{printed, run, run_with};
+
+#[test]
+fn abs_keeps_the_type() {
+    assert_eq!(printed("
+func main()
+[
+print abs(0 - 5), abs(2.5 - 4.0), abs(3i64 - 10i64), abs(0 - 2147483647 - 1);
+]
+"), ["5 1.5 7 2147483648"]);
+}
+
+#[test]
+fn min_and_max_promote_to_the_common_type() {
+    assert_eq!(printed("
+func main()
+[
+print min(3, 7), max(3, 7), min(2, 1.5), max(2, 1.5), max(3u8, 300), max(1, 2.0f64);
+]
+"), ["3 7 1.5 2.0 300 2.0"]);
+}
+
+#[test]
+fn pow_stays_integer_for_natural_exponents() {
+    assert_eq!(printed("
+func main()
+[
+print pow(2, 10), pow(2, 100), pow(3i64, 3);
+print pow(2.0, 0.5), pow(2, 0 - 1);
+]
+"), ["1024 1267650600228229401496703205376 27", "1.4142135 0.5"]);
+
+    let output = run_with("
+func main()
+[
+print pow(2, 40);
+]
+", &["--overflow", "trap"]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Integer overflow"));
+}
+
+#[test]
+fn rounding_leaves_integers_alone() {
+    assert_eq!(printed("
+func main()
+[
+print floor(2.7), ceil(2.2), round(2.5), floor(7);
+]
+"), ["2.0 3.0 3.0 7"]);
+}
+
+#[test]
+fn transcendental_functions_return_floats() {
+    assert_eq!(printed("
+func main()
+[
+print sqrt(16), sqrt(2.0f64), sin(0), cos(0.0), log(1), exp(1);
+]
+"), ["4.0 1.4142135623730951 0.0 1.0 0.0 2.7182817"]);
+}
+
+#[test]
+fn programs_may_define_their_own_versions() {
+    assert_eq!(printed("
+func abs(n)
+[
+return 42;
+]
+func main()
+[
+print abs(0 - 1);
+]
+"), ["42"]);
+}
+
+#[test]
+fn bad_arguments_are_runtime_errors() {
+    for (call, message) in [
+        ("sqrt(0 - 1)", "'sqrt' is undefined for -1"),
+        ("log(0)", "'log' is undefined for 0"),
+        ("abs(\"x\")", "Function 'abs' expects a number but got Str"),
+    ] {
+        let output = run(&format!("func main()\n[\nprint {call};\n]\n"));
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(message), "{stderr}");
+    }
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use common::load;
+use PL_Final::{Error, Runtime, RuntimeError, Value};
+
+fn doubling() -> Runtime {
+    let mut runtime = Runtime::new();
+    runtime.register("double", 1, |_, arguments| {
+        match &arguments[0] {
+            Value::I32(n) => { Ok(Value::I32(n * 2)) }
+            other => { Err(RuntimeError::InvalidNumber { text: other.to_string(), target: "i32" }) }
+        }
+    });
+    runtime
+}
+
+#[test]
+fn programs_call_natives_like_functions() {
+    let machine = load(doubling(), "
+func quadruple(n)
+[
+return double(double(n));
+]
+func main()
+[
+]
+").unwrap();
+    assert_eq!(machine.call("quadruple", vec![Value::I32(5)]).unwrap().to_string(), "20");
+    assert_eq!(machine.call("double", vec![Value::I32(4)]).unwrap().to_string(), "8");
+}
+
+#[test]
+fn natives_keep_host_state() {
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    let mut runtime = Runtime::new();
+    runtime.register("tick", 0, move |_, _| {
+        counter.set(counter.get() + 1);
+        Ok(Value::Nil)
+    });
+    let machine = load(runtime, "
+func main()
+[
+let ignored;
+ignored = tick();
+ignored = tick();
+]
+").unwrap();
+    machine.run().unwrap();
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn native_errors_carry_the_call_site() {
+    let machine = load(doubling(), "
+func main()
+[
+print double(\"x\");
+]
+").unwrap();
+    let error = machine.run().unwrap_err();
+    assert_eq!(error.to_string(), "Runtime error: Cannot convert \"x\" to i32");
+    let trace: Vec<String> = error.stack_trace().iter().map(|site| site.to_string()).collect();
+    assert_eq!(trace, ["double (called from line 4)", "main"]);
+}
+
+#[test]
+fn arity_is_checked_before_running() {
+    let error = load(doubling(), "
+func main()
+[
+print double(1, 2);
+]
+").err().unwrap();
+    assert_eq!(error.to_string(),
+        "Analysis error: Function 'double' expects 1 arguments but got 2 in function main");
+
+    let error = load(Runtime::new(), "
+func add(a, b)
+[
+return a + b;
+]
+func main()
+[
+if false
+[
+print add(1);
+]
+]
+").err().unwrap();
+    assert!(matches!(error, Error::Analysis(_)), "{error}");
+}
+
+#[test]
+fn program_functions_hide_natives() {
+    let machine = load(doubling(), "
+func double(n)
+[
+return n + n + 1;
+]
+func main()
+[
+]
+").unwrap();
+    assert_eq!(machine.call("double", vec![Value::I32(4)]).unwrap().to_string(), "9");
+}
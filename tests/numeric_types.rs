@@ -0,0 +1,130 @@
+mod common;
+
+use common::{printed, printed_with, run, run_with};
+
+#[test]
+fn suffixed_literals_have_their_type() {
+    assert_eq!(printed("
+func main()
+[
+print 7i64;
+print 255u8;
+print 4000000000u32;
+print 18446744073709551615u64;
+print 2.5f64;
+print 3i32;
+]
+"), ["7", "255", "4000000000", "18446744073709551615", "2.5", "3"]);
+}
+
+#[test]
+fn mixed_widths_use_the_common_type() {
+    assert_eq!(printed("
+func main()
+[
+print 200u8 + 50u8;
+print 1u8 + 1u32;
+print 1u8 - 2;
+print 1u32 - 2;
+print 7i64 * 3;
+print 5u64 / 2;
+print 1.5f64 + 1;
+print 1.5 + 1.5f64;
+print 5u8 < 300;
+print 3u64 == 3.0f64;
+]
+"), [
+        "250", "2", "-1", "-1", "21",
+        "2", "2.5", "3.0", "true", "true",
+    ]);
+}
+
+#[test]
+fn declared_types_choose_the_width() {
+    assert_eq!(printed("
+func half(n: u8)
+[
+return n / 2u8;
+]
+func main()
+[
+let big: u64;
+let wide: f64;
+big = 18446744073709551615;
+wide = 3;
+print big;
+print wide;
+print half(200);
+]
+"), ["18446744073709551615", "3.0", "100"]);
+}
+
+#[test]
+fn values_that_do_not_fit_are_runtime_errors() {
+    let output = run("
+func main()
+[
+let small: u8;
+small = 300;
+]
+");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Cannot convert i32 300 to u8"));
+
+    let output = run("
+func twice(n: i32)
+[
+return n * 2;
+]
+func main()
+[
+print twice(1.5);
+]
+");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Cannot convert f32 1.5 to i32"));
+}
+
+#[test]
+fn narrow_overflow_follows_the_overflow_mode() {
+    let source = "
+func main()
+[
+print 255u8 + 1u8;
+]
+";
+    assert_eq!(printed_with(source, &["--overflow", "wrap"]), ["0"]);
+    assert_eq!(printed_with(source, &["--overflow", "saturate"]), ["255"]);
+    for flags in [&[][..], &["--overflow", "trap"]] {
+        let output = run_with(source, flags);
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Integer overflow in 'Add' of 255 and 1"));
+    }
+}
+
+#[test]
+fn only_i32_promotes_to_a_big_integer() {
+    let output = run("
+func main()
+[
+print 5u8 - 10u8;
+]
+");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Integer overflow in 'Sub' of 5 and 10"));
+
+    let output = run("
+func main()
+[
+print 9223372036854775807i64 + 1i64;
+]
+");
+    assert!(!output.status.success());
+
+    assert_eq!(printed("
+func main()
+[
+print 2147483647 + 1, 1u8 + 2147483647;
+]
+"), ["2147483648 2147483648"]);
+}
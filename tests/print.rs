@@ -0,0 +1,96 @@
+mod common;
+
+use common::{printed, run, run_with_input, stdout_of};
+
+#[test]
+fn values_print_without_decoration() {
+    assert_eq!(printed("
+func nothing()
+[
+let x;
+]
+func main()
+[
+print 5;
+print 2.5;
+print 3.0;
+print true;
+print 'c';
+print \"hello world\";
+print nothing();
+print 99999999999999999999;
+]
+"), ["5", "2.5", "3.0", "true", "c", "hello world", "nil", "99999999999999999999"]);
+}
+
+#[test]
+fn print_separates_arguments_with_spaces() {
+    assert_eq!(printed("
+func add(a, b)
+[
+return a + b;
+]
+func main()
+[
+print 1, \"and\", 2;
+print add(1, 2), add(3, 4);
+]
+"), ["1 and 2", "3 7"]);
+}
+
+#[test]
+fn print_fills_format_placeholders() {
+    assert_eq!(printed("
+func main()
+[
+let n;
+n = 5;
+print \"{} squared is {}\", n, n * n;
+print \"{{}} stays, {} goes\", 1;
+]
+"), ["5 squared is 25", "{} stays, 1 goes"]);
+}
+
+#[test]
+fn only_string_literals_are_templates() {
+    let output = run_with_input("
+func main()
+[
+let s;
+s = read_line();
+print s;
+print s, 1;
+print \"{}\";
+]
+", &[], "json {} here\n");
+    assert_eq!(stdout_of(&output), "json {} here\njson {} here 1\n{}\n");
+}
+
+#[test]
+fn placeholder_count_must_match() {
+    let output = run("
+func main()
+[
+print \"{} and {}\", 1;
+]
+");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Format string has 2 placeholders but 1 arguments were given"));
+}
+
+#[test]
+fn output_and_error_report_use_separate_streams() {
+    let output = run("
+func main()
+[
+print \"before\";
+print 1 / 0;
+]
+");
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "before\n");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("Runtime error: Division by zero"), "{stderr}");
+    assert!(stderr.contains("    in main"), "{stderr}");
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::{printed_with, run};
+use PL_Final::{Interpreter, Runtime, Value};
+
+const DRAWS: &str = "
+func main()
+[
+print random_int(1, 6), random_int(1, 1000000), random_float(), shuffle(split(\"abcdef\", \"\"));
+]
+";
+
+#[test]
+fn a_seed_repeats_the_run() {
+    let first = printed_with(DRAWS, &["--seed", "7"]);
+    assert_eq!(first, printed_with(DRAWS, &["--seed", "7"]));
+    assert_ne!(first, printed_with(DRAWS, &["--seed", "8"]));
+}
+
+#[test]
+fn a_seed_can_be_set_on_the_runtime() {
+    let draw = |seed| {
+        let mut runtime = Runtime::new();
+        runtime.set_seed(seed);
+        let machine = Interpreter::with_runtime(runtime).load_source(DRAWS).unwrap();
+        machine.call("random_int", vec![Value::I32(0), Value::I32(1_000_000)]).unwrap().to_string()
+    };
+    assert_eq!(draw(1), draw(1));
+}
+
+#[test]
+fn draws_stay_in_range() {
+    let lines = printed_with("
+func main()
+[
+let i;
+let n;
+let x;
+i = 0;
+while i < 200
+[
+n = random_int(0 - 2, 2);
+x = random_float();
+if (n < 0 - 2) or (n > 2) or (x < 0.0) or (x >= 1.0)
+[
+print \"out of range\", n, x;
+]
+i = i + 1;
+]
+print choice(split(\"q\", \"\"));
+]
+", &["--seed", "3"]);
+    assert_eq!(lines, ["q"]);
+}
+
+#[test]
+fn shuffle_keeps_every_item() {
+    let lines = printed_with("
+func main()
+[
+print shuffle(split(\"abcdef\", \"\"));
+]
+", &["--seed", "11"]);
+    let mut items: Vec<&str> = lines[0].trim_matches(['[', ']']).split(", ").collect();
+    items.sort();
+    assert_eq!(items, ["a", "b", "c", "d", "e", "f"]);
+}
+
+#[test]
+fn empty_ranges_are_errors() {
+    let output = run("
+func main()
+[
+print random_int(5, 1);
+]
+");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Range from 5 to 1 is empty"));
+
+    let output = run("
+func main()
+[
+print choice(split(\"\", \"\"));
+]
+");
+    assert!(!output.status.success());
+}
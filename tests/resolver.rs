@@ -0,0 +1,127 @@
+mod common;
+
+use common::{printed, stderr_of};
+
+#[test]
+fn lets_in_nested_blocks_share_the_function_frame() {
+    assert_eq!(printed("
+func main()
+[
+if true
+[
+let inner;
+inner = 4;
+]
+inner = inner + 1;
+print inner;
+let i;
+i = 0;
+while i < 3
+[
+let last;
+last = i;
+i = i + 1;
+]
+print last;
+]
+"), ["5", "2"]);
+}
+
+#[test]
+fn sibling_blocks_can_declare_the_same_name() {
+    assert_eq!(printed("
+func main()
+[
+if true
+[
+let t;
+t = 1;
+print t;
+]
+else
+[
+let t;
+t = 2;
+print t;
+]
+]
+"), ["1"]);
+
+    let stderr = stderr_of("
+func main()
+[
+let t;
+if true
+[
+let t;
+]
+]
+", &[]);
+    assert_eq!(stderr, "Analysis error: Duplicate identifier 't' in function main\n");
+}
+
+#[test]
+fn a_local_hides_a_global_only_in_its_function() {
+    assert_eq!(printed("
+func square(n)
+[
+return n * n;
+]
+func other()
+[
+return square(3);
+]
+func main()
+[
+let square;
+square = 7;
+print square, other();
+]
+"), ["7 9"]);
+}
+
+#[test]
+fn assignments_reach_globals_one_frame_up() {
+    assert_eq!(printed("
+func twice(n)
+[
+return n * 2;
+]
+func thrice(n)
+[
+return n * 3;
+]
+func swap()
+[
+twice = thrice;
+return 0;
+]
+func main()
+[
+let ignored;
+print twice(5);
+ignored = swap();
+print twice(5);
+]
+"), ["10", "15"]);
+}
+
+#[test]
+fn undeclared_names_are_rejected_before_running() {
+    let stderr = stderr_of("
+func main()
+[
+print \"before\";
+print missing;
+]
+", &[]);
+    assert_eq!(stderr, "Analysis error: Undeclared identifier 'missing' in function main\n");
+
+    let stderr = stderr_of("
+func main()
+[
+missing = 1;
+]
+", &[]);
+    assert_eq!(stderr, "Analysis error: Undeclared identifier 'missing' in function main\n");
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use std::process::Command;
+
+use common::{interpreter, launch, stdout_of};
+
+#[test]
+fn inline_source_runs_with_every_positional_as_an_argument() {
+    let output = interpreter(&["-c", "func main(all) [ print len(all), all; ]", "one", "two"], "");
+    assert_eq!(stdout_of(&output), "2 [one, two]\n");
+
+    let output = interpreter(&["-c", "func main() [ print 6 * 7; ]"], "");
+    assert_eq!(stdout_of(&output), "42\n");
+}
+
+#[test]
+fn a_dash_reads_the_program_from_stdin() {
+    let output = interpreter(&["-", "5"], "
+func main(n: i32)
+[
+print n * n;
+]
+");
+    assert_eq!(stdout_of(&output), "25\n");
+}
+
+#[test]
+fn a_shebang_line_is_skipped() {
+    let output = launch("#!/usr/bin/env PL_Final
+func main()
+[
+print missing;
+]
+", &[], &[], "");
+    assert_eq!(output.status.code(), Some(65));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Undeclared identifier"), "{:?}", output);
+
+    let output = launch("#!/usr/bin/env PL_Final\nfunc main() [ print \"ok\"; ]\n", &[], &[], "");
+    assert_eq!(stdout_of(&output), "ok\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn scripts_with_a_shebang_are_executable() {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = std::env::temp_dir().join(format!("tbd_script_{}.tbd", std::process::id()));
+    fs::write(&path, format!("#!{}\nfunc main(name) [ print \"hello\", name; ]\n", env!("CARGO_BIN_EXE_PL_Final"))).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    // another test thread forking while the script was open for writing makes exec fail
+    // with "text file busy" until that child has exec'd too, so retry briefly
+    let output = (0..50)
+        .find_map(|_| {
+            match Command::new(&path).arg("world").output() {
+                Err(error) if error.raw_os_error() == Some(26) => {
+                    std::thread::sleep(std::time::Duration::from_millis(20));
+                    None
+                }
+                result => { Some(result.expect("Failed to launch script.")) }
+            }
+        })
+        .expect("Script stayed busy.");
+    fs::remove_file(&path).ok();
+    assert_eq!(stdout_of(&output), "hello [world]\n");
+}
+
+#[test]
+fn unreadable_files_have_their_own_code() {
+    let output = interpreter(&["/nonexistent/program.tbd"], "");
+    assert_eq!(output.status.code(), Some(66));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Cannot read /nonexistent/program.tbd"));
+}
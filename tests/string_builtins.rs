@@ -0,0 +1,68 @@
+mod common;
+
+use common::{printed, run};
+
+#[test]
+fn measuring_and_slicing() {
+    assert_eq!(printed("
+func main()
+[
+let s;
+s = \"  Hello, World  \";
+print len(s), len(trim(s)), \"[\" + trim(s) + \"]\";
+s = trim(s);
+print substr(s, 7, 5), char_at(s, 0), index_of(s, \"World\"), index_of(s, 'z');
+]
+"), ["16 12 [Hello, World]", "World H 7 -1"]);
+}
+
+#[test]
+fn split_gives_a_list() {
+    assert_eq!(printed("
+func main()
+[
+let parts;
+parts = split(\"a,b,,c\", \",\");
+print parts, len(parts), get(parts, 1);
+print split(\"abc\", \"\");
+]
+"), ["[a, b, , c] 4 b", "[a, b, c]"]);
+}
+
+#[test]
+fn case_and_replacement() {
+    assert_eq!(printed("
+func main()
+[
+print to_upper(\"Hello\"), to_lower(\"Hello\"), replace(\"Hello\", \"l\", \"L\");
+]
+"), ["HELLO hello HeLLo"]);
+}
+
+#[test]
+fn conversions_to_and_from_text() {
+    assert_eq!(printed("
+func main()
+[
+print to_string(12) + to_string(3.5), parse_int(\" 42 \") + 1, parse_float(\"2.5\") * 2;
+print parse_int(\"123456789012345678901234567890\");
+]
+"), ["123.5 43 5.0", "123456789012345678901234567890"]);
+}
+
+#[test]
+fn failures_are_runtime_errors() {
+    for (call, message) in [
+        ("parse_int(\"4x\")", "Cannot convert \"4x\" to integer"),
+        ("parse_float(\"\")", "Cannot convert \"\" to f32"),
+        ("char_at(\"abc\", 3)", "Index 3 is out of range for length 3"),
+        ("substr(\"abc\", 1, 5)", "Index 6 is out of range for length 3"),
+        ("get(split(\"a\", \",\"), 0 - 1)", "Index -1 is out of range for length 1"),
+        ("len(5)", "Function 'len' expects a string but got I32"),
+    ] {
+        let output = run(&format!("func main()\n[\nprint {call};\n]\n"));
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(message), "{call}: {stderr}");
+    }
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use common::{printed, run};
+
+#[test]
+fn one_letter_strings_and_chars_are_different() {
+    assert_eq!(printed("
+func main()
+[
+print 'a';
+print \"a\";
+print \"a\" + \"b\";
+print 'a' as string + \"b\";
+]
+"), ["a", "a", "ab", "ab"]);
+}
+
+#[test]
+fn chars_do_code_arithmetic() {
+    assert_eq!(printed("
+func main()
+[
+print 'a' + 1;
+print 2 + 'a';
+print 'z' - 1;
+print 'z' - 'a';
+print 'a' < 'b';
+print 'a' as i32;
+]
+"), ["b", "c", "y", "25", "true", "97"]);
+
+    let output = run("
+func main()
+[
+print 'a' * 2;
+]
+");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Cannot perform 'Mul' on Char and I32"));
+}
+
+#[test]
+fn strings_compare_lexicographically() {
+    assert_eq!(printed("
+func main()
+[
+print \"apple\" == \"apple\";
+print \"apple\" != \"apples\";
+print \"apple\" < \"banana\";
+print \"apple\" < \"apples\";
+print \"b\" > \"abc\";
+print \"B\" >= \"a\";
+print \"abc\" <= \"abc\";
+]
+"), ["true", "true", "true", "true", "true", "false", "true"]);
+}
+
+#[test]
+fn chars_and_strings_do_not_mix() {
+    let output = run("
+func main()
+[
+print 'a' == \"a\";
+]
+");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Cannot perform 'Equal' on Char and Str"));
+}